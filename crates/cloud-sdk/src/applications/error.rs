@@ -9,6 +9,14 @@ pub enum ApplicationsError {
     #[error("Application not found: {name}")]
     ApplicationNotFound { name: String },
 
+    /// An application can't be turned back into the manifest it was deployed with
+    #[error("Cannot rebuild the application manifest: {0}")]
+    CannotRebuildManifest(String),
+
+    /// Failed to write the application code zip
+    #[error("Code zip error: {0}")]
+    CodeZip(String),
+
     /// Function call not found
     #[error("Function call not found: {id}")]
     FunctionCallNotFound { id: String },
@@ -17,6 +25,10 @@ pub enum ApplicationsError {
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
 
+    /// Output type hints are not valid base64
+    #[error("Invalid output type hints: {0}")]
+    InvalidOutputTypeHints(String),
+
     /// Invalid request data
     #[error("Invalid request data: {0}")]
     InvalidRequest(String),
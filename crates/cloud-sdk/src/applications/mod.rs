@@ -15,14 +15,14 @@
 //!
 //! // List applications in a namespace
 //! let request = ListApplicationsRequest::builder()
-//!     .namespace("default".to_string())
+//!     .namespace("default".into())
 //!     .build()?;
 //! let apps = apps_client.list(&request).await?;
 //!
 //! // Get a specific application
 //! let app = apps_client.get(&GetApplicationRequest::builder()
-//!     .namespace("default".to_string())
-//!     .application("my-app".to_string())
+//!     .namespace("default".into())
+//!     .application("my-app".into())
 //!     .build()?).await?;
 //!
 //! Ok(())
@@ -31,15 +31,51 @@
 
 pub mod error;
 pub mod models;
+mod namespaced;
+
+pub use namespaced::NamespacedApplicationsClient;
 
 use bytes::Bytes;
+use futures::{FutureExt, Stream, StreamExt, TryStreamExt, future::BoxFuture, ready, stream};
 use reqwest::{
     Method, StatusCode,
-    header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE},
+    header::{ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, HeaderValue, IF_MATCH},
     multipart::{Form, Part},
 };
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+use uuid::Uuid;
+
+use crate::{
+    applications::{error::ApplicationsError, models::RequestStateChangeEvent},
+    bulk::BulkResult,
+    client::{
+        Client, EventStream, content_encodings, deserialize_json, insert_headers,
+        is_json_content_type, read_decoded_body, urlencode, with_timeout,
+    },
+    codec::{json_array_stream, ndjson_stream},
+    error::SdkError,
+};
 
-use crate::{applications::models::RequestStateChangeEvent, client::Client, error::SdkError};
+// Route templates of the endpoints, reported to metrics hooks and middlewares.
+const APPLICATIONS_ROUTE: &str = "/v1/namespaces/{namespace}/applications";
+const APPLICATION_ROUTE: &str = "/v1/namespaces/{namespace}/applications/{application}";
+const REQUESTS_ROUTE: &str = "/v1/namespaces/{namespace}/applications/{application}/requests";
+const REQUEST_ROUTE: &str =
+    "/v1/namespaces/{namespace}/applications/{application}/requests/{request_id}";
+const FUNCTION_RUN_ROUTE: &str = "/v1/namespaces/{namespace}/applications/{application}/requests/{request_id}/function-runs/{function_run_id}";
+const FUNCTION_OUTPUT_ROUTE: &str = "/v1/namespaces/{namespace}/applications/{application}/requests/{request_id}/output/{function_call_id}";
+const REQUEST_OUTPUT_ROUTE: &str =
+    "/v1/namespaces/{namespace}/applications/{application}/requests/{request_id}/output";
+const LOGS_ROUTE: &str = "/v1/namespaces/{namespace}/applications/{application}/logs";
+const PROGRESS_UPDATES_ROUTE: &str =
+    "/v1/namespaces/{namespace}/applications/{application}/requests/{request_id}/updates";
 
 /// A client for interacting with Tensorlake Cloud applications.
 ///
@@ -74,6 +110,42 @@ impl ApplicationsClient {
         Self { client }
     }
 
+    /// Bind the client to a namespace.
+    ///
+    /// The methods of the returned client don't take a namespace, which is
+    /// convenient when working within a single namespace.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace to bind the client to
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApplicationsError::InvalidRequest` if the namespace is empty or
+    /// only whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client).for_namespace(&"default".into())?;
+    ///     let app = apps_client.get(&"my-app".into()).await?;
+    ///     println!("Version: {}", app.version);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn for_namespace(
+        self,
+        namespace: &models::Namespace,
+    ) -> Result<NamespacedApplicationsClient, SdkError> {
+        NamespacedApplicationsClient::new(self, namespace)
+    }
+
     /// List all applications in a namespace.
     ///
     /// # Arguments
@@ -94,12 +166,11 @@ impl ApplicationsClient {
     ///         .bearer_token("your-api-key")
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
-    ///     let request = tensorlake_cloud_sdk::applications::models::ListApplicationsRequest {
-    ///         namespace: "default".to_string(),
-    ///         limit: Some(10),
-    ///         cursor: None,
-    ///         direction: None,
-    ///     };
+    ///     let request = tensorlake_cloud_sdk::applications::models::ListApplicationsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .limit(10)
+    ///         .tag_filters(vec![("env".to_string(), "prod".to_string())])
+    ///         .build()?;
     ///     apps_client.list(&request).await?;
     ///     Ok(())
     /// }
@@ -108,7 +179,87 @@ impl ApplicationsClient {
         &self,
         request: &models::ListApplicationsRequest,
     ) -> Result<models::ApplicationsList, SdkError> {
-        let uri_str = format!("/v1/namespaces/{}/applications", request.namespace);
+        with_timeout(request.timeout, self.list_inner(request)).await
+    }
+
+    async fn list_inner(
+        &self,
+        request: &models::ListApplicationsRequest,
+    ) -> Result<models::ApplicationsList, SdkError> {
+        let mut req = self.list_request(request).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, APPLICATIONS_ROUTE).await?;
+
+        let mut list: models::ApplicationsList = deserialize_json(resp).await?;
+        list.applications
+            .retain(|application| application.has_tags(&request.tag_filters));
+
+        Ok(list)
+    }
+
+    /// List the applications in a namespace, deserializing them one at a time.
+    ///
+    /// Unlike [`ApplicationsClient::list`], the response is never held in memory
+    /// as a whole, which matters for namespaces with many applications. The
+    /// pagination cursor of the response is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The list applications request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the applications in the specified namespace.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::ListApplicationsRequest};
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = ListApplicationsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .limit(1000)
+    ///         .build()?;
+    ///     let mut applications = apps_client.list_stream(&request).await?;
+    ///     while let Some(application) = applications.next().await {
+    ///         println!("{}", application?.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_stream(
+        &self,
+        request: &models::ListApplicationsRequest,
+    ) -> Result<EventStream<models::Application>, SdkError> {
+        with_timeout(request.timeout, self.list_stream_inner(request)).await
+    }
+
+    async fn list_stream_inner(
+        &self,
+        request: &models::ListApplicationsRequest,
+    ) -> Result<EventStream<models::Application>, SdkError> {
+        let mut req = self.list_request(request).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, APPLICATIONS_ROUTE).await?;
+
+        let tag_filters = request.tag_filters.clone();
+        let applications = json_array_stream::<models::Application>(resp, "applications")
+            .try_filter(move |application| {
+                futures::future::ready(application.has_tags(&tag_filters))
+            });
+        Ok(EventStream::new(applications))
+    }
+
+    fn list_request(
+        &self,
+        request: &models::ListApplicationsRequest,
+    ) -> reqwest_middleware::RequestBuilder {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications",
+            urlencode(&request.namespace)
+        );
         let mut req_builder = self.client.request(Method::GET, &uri_str);
 
         if let Some(ref param_value) = request.limit {
@@ -120,15 +271,7 @@ impl ApplicationsClient {
         if let Some(ref param_value) = request.direction {
             req_builder = req_builder.query(&[("direction", param_value)]);
         }
-
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
-
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_slice(bytes.as_ref());
-        let list = serde_path_to_error::deserialize(jd)?;
-
-        Ok(list)
+        req_builder.query(&request.extra_query)
     }
 
     /// Get details of a specific application.
@@ -152,8 +295,8 @@ impl ApplicationsClient {
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
     ///     let request = GetApplicationRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
     ///         .build()?;
     ///     apps_client.get(&request).await?;
     ///     Ok(())
@@ -162,25 +305,143 @@ impl ApplicationsClient {
     pub async fn get(
         &self,
         request: &models::GetApplicationRequest,
+    ) -> Result<models::Application, SdkError> {
+        with_timeout(request.timeout, self.get_inner(request)).await
+    }
+
+    async fn get_inner(
+        &self,
+        request: &models::GetApplicationRequest,
     ) -> Result<models::Application, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}",
-            request.namespace, request.application
+            urlencode(&request.namespace),
+            urlencode(&request.application)
         );
         let req_builder = self.client.request(Method::GET, &uri_str);
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, APPLICATION_ROUTE).await?;
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let app = serde_path_to_error::deserialize(jd)?;
+        let app = deserialize_json(resp).await?;
 
         Ok(app)
     }
 
+    /// Get the details of several applications concurrently.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the applications
+    /// * `names` - The names of the applications to get
+    /// * `concurrency` - The maximum number of requests in flight at the same time
+    ///
+    /// # Returns
+    ///
+    /// Returns the result of each lookup, in the same order as `names`.
+    /// A failed lookup doesn't stop the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `concurrency` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let names = vec!["my-app".into(), "other-app".into()];
+    ///     for app in apps_client.get_many(&"default".into(), &names, 4).await? {
+    ///         println!("{}", app?.name);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_many(
+        &self,
+        namespace: &models::Namespace,
+        names: &[models::ApplicationName],
+        concurrency: usize,
+    ) -> Result<BulkResult<models::Application>, SdkError> {
+        if concurrency == 0 {
+            return Err(ApplicationsError::InvalidRequest(
+                "concurrency must be greater than zero".to_string(),
+            )
+            .into());
+        }
+
+        let lookups = names.iter().map(|name| {
+            let request = models::GetApplicationRequest {
+                namespace: namespace.clone(),
+                application: name.clone(),
+                headers: Vec::new(),
+                timeout: None,
+            };
+            async move { self.get(&request).await }
+        });
+
+        let results: Vec<_> = stream::iter(lookups).buffered(concurrency).collect().await;
+        Ok(results.into())
+    }
+
+    /// Get the entrypoint of an application.
+    ///
+    /// The entrypoint includes the entrypoint function name, its serializers, and its
+    /// output type hints. Use this instead of [`ApplicationsClient::get`] when you only
+    /// need the application's input and output schema.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    ///
+    /// # Returns
+    ///
+    /// Returns the entrypoint manifest of the application.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let entrypoint = apps_client.get_schema(&"default".into(), &"my-app".into()).await?;
+    ///     println!("Entrypoint: {}", entrypoint.function_name);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_schema(
+        &self,
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+    ) -> Result<models::EntryPointManifest, SdkError> {
+        let request = models::GetApplicationRequest {
+            namespace: namespace.clone(),
+            application: application.clone(),
+            headers: Vec::new(),
+            timeout: None,
+        };
+        let app = self.get(&request).await?;
+
+        Ok(app.entrypoint)
+    }
+
     /// Create or update an application.
     ///
+    /// Set `expected_version` on the request to only update the application if its
+    /// current version matches. The version is sent as an entity tag in the
+    /// `If-Match` header, and a mismatch returns [`SdkError::PreconditionFailed`].
+    ///
     /// # Arguments
     ///
     /// * `request` - The upsert application request
@@ -203,7 +464,7 @@ impl ApplicationsClient {
     ///         .version("1.0.0")
     ///         .build()?;
     ///     let request = UpsertApplicationRequest::builder()
-    ///         .namespace("default")
+    ///         .namespace("default".into())
     ///         .application_manifest(app_data)
     ///         .code_zip(code_zip)
     ///         .build()?;
@@ -212,19 +473,101 @@ impl ApplicationsClient {
     /// }
     /// ```
     pub async fn upsert(&self, request: &models::UpsertApplicationRequest) -> Result<(), SdkError> {
+        with_timeout(
+            request.timeout,
+            self.upsert_with_code(
+                &request.namespace,
+                &request.application_manifest,
+                request.expected_version.as_deref(),
+                &request.headers,
+                Part::bytes(request.code_zip.clone()),
+            ),
+        )
+        .await
+    }
+
+    /// Create or update an application, streaming its code zip from a reader.
+    ///
+    /// Unlike [`ApplicationsClient::upsert`], the code zip isn't held in memory,
+    /// which suits large applications. Since the body can't be replayed, the
+    /// upload isn't retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The upsert application request
+    /// * `code_zip` - A reader of the code zip, such as an open file
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::{UpsertApplicationFromReaderRequest, ApplicationManifest}}};
+    ///
+    /// async fn example(code_zip: impl tokio::io::AsyncRead + Send + 'static) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let app_data = ApplicationManifest::builder()
+    ///         .name("my-app")
+    ///         .version("1.0.0")
+    ///         .build()?;
+    ///     let request = UpsertApplicationFromReaderRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application_manifest(app_data)
+    ///         .build()?;
+    ///     apps_client.upsert_from_reader(&request, code_zip).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn upsert_from_reader<R>(
+        &self,
+        request: &models::UpsertApplicationFromReaderRequest,
+        code_zip: R,
+    ) -> Result<(), SdkError>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let body = reqwest::Body::wrap_stream(ReaderStream::new(code_zip));
+        with_timeout(
+            request.timeout,
+            self.upsert_with_code(
+                &request.namespace,
+                &request.application_manifest,
+                request.expected_version.as_deref(),
+                &request.headers,
+                Part::stream(body),
+            ),
+        )
+        .await
+    }
+
+    async fn upsert_with_code(
+        &self,
+        namespace: &str,
+        application_manifest: &models::ApplicationManifest,
+        expected_version: Option<&str>,
+        headers: &[(String, String)],
+        code: Part,
+    ) -> Result<(), SdkError> {
         let mut multipart_form = Form::new();
 
-        let manifest_json = serde_json::to_string(&request.application_manifest)?;
+        let manifest_json = serde_json::to_string(application_manifest)?;
         multipart_form = multipart_form.text("application", manifest_json);
+        multipart_form = multipart_form.part("code", code.file_name("code.zip"));
 
-        let file_part = Part::bytes(request.code_zip.clone()).file_name("code.zip");
-        multipart_form = multipart_form.part("code", file_part);
-
-        let uri_str = format!("/v1/namespaces/{}/applications", request.namespace);
-        let req = self
-            .client
-            .build_multipart_request(Method::POST, &uri_str, multipart_form)?;
-        let _resp = self.client.execute(req).await?;
+        let uri_str = format!("/v1/namespaces/{}/applications", urlencode(namespace));
+        let mut req =
+            self.client
+                .build_multipart_request(Method::POST, &uri_str, multipart_form)?;
+        insert_headers(&mut req, headers)?;
+        if let Some(expected_version) = expected_version {
+            req.headers_mut().insert(
+                IF_MATCH,
+                HeaderValue::from_str(&format!("\"{expected_version}\""))
+                    .map_err(|e| SdkError::InvalidHeaderValue(e.to_string()))?,
+            );
+        }
+        let _resp = self.client.execute_route(req, APPLICATIONS_ROUTE).await?;
 
         Ok(())
     }
@@ -246,22 +589,31 @@ impl ApplicationsClient {
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
     ///     let request = DeleteApplicationRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
     ///         .build()?;
     ///     apps_client.delete(&request).await?;
     ///     Ok(())
     /// }
     /// ```
     pub async fn delete(&self, request: &models::DeleteApplicationRequest) -> Result<(), SdkError> {
+        with_timeout(request.timeout, self.delete_inner(request)).await
+    }
+
+    async fn delete_inner(
+        &self,
+        request: &models::DeleteApplicationRequest,
+    ) -> Result<(), SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}",
-            request.namespace, request.application
+            urlencode(&request.namespace),
+            urlencode(&request.application)
         );
         let req_builder = self.client.request(Method::DELETE, &uri_str);
 
-        let req = req_builder.build()?;
-        let _resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let _resp = self.client.execute_route(req, APPLICATION_ROUTE).await?;
 
         Ok(())
     }
@@ -276,10 +628,16 @@ impl ApplicationsClient {
     ///
     /// If `stream` is false, returns the request ID. If `stream` is true, returns a stream of progress events.
     ///
+    /// # Errors
+    ///
+    /// Returns `ApplicationsError::InvalidRequest` with the server's error message if the
+    /// server rejects the request body, for example because it doesn't match the
+    /// entrypoint schema.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::{InvokeApplicationRequest, InvokeResponse}}};
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::InvokeApplicationRequest}};
     /// use serde_json;
     ///
     /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
@@ -289,194 +647,533 @@ impl ApplicationsClient {
     ///     let apps_client = ApplicationsClient::new(client);
     ///     let data = serde_json::json!({"input": "hello world"});
     ///     let request = InvokeApplicationRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
     ///         .body(data)
     ///         .build()?;
-    ///     let response = apps_client.invoke(&request).await?;
-    ///     match response {
-    ///         InvokeResponse::RequestId(id) => println!("Request ID: {}", id),
-    ///         InvokeResponse::Stream(_) => unreachable!(),
-    ///     }
+    ///     let request_id = apps_client.invoke(&request).await?.into_request_id()?;
+    ///     println!("Request ID: {}", request_id);
     ///     Ok(())
     /// }
     /// ```
     pub async fn invoke(
         &self,
         request: &models::InvokeApplicationRequest,
+    ) -> Result<models::InvokeResponse, SdkError> {
+        with_timeout(request.timeout, self.invoke_inner(request)).await
+    }
+
+    async fn invoke_inner(
+        &self,
+        request: &models::InvokeApplicationRequest,
     ) -> Result<models::InvokeResponse, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}",
-            request.namespace, request.application
+            urlencode(&request.namespace),
+            urlencode(&request.application)
         );
         let req_builder = self.client.request(Method::POST, &uri_str);
-        let req = req_builder
+        let mut req = req_builder
             .header(ACCEPT, "application/json")
             .json(&request.body)
             .build()?;
-        let resp = self.client.execute(req).await?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self
+            .client
+            .execute_route(req, APPLICATION_ROUTE)
+            .await
+            .map_err(|error| match error {
+                SdkError::ServerError { status, message } if status == StatusCode::BAD_REQUEST => {
+                    ApplicationsError::InvalidRequest(message).into()
+                }
+                error => error,
+            })?;
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_slice(&bytes);
-        let request_id_resp: serde_json::Value = serde_path_to_error::deserialize(jd)?;
-        let request_id =
-            request_id_resp["request_id"]
-                .as_str()
-                .ok_or_else(|| SdkError::ServerError {
-                    status: reqwest::StatusCode::OK,
-                    message: "Missing request_id in response".to_string(),
-                })?;
-        Ok(models::InvokeResponse::RequestId(request_id.to_string()))
+        let ack: models::InvokeAck = deserialize_json(resp).await?;
+        Ok(models::InvokeResponse::RequestId(ack.request_id))
     }
 
-    /// List requests for an application.
+    /// Invoke an application with named arguments for its entrypoint.
+    ///
+    /// The application is fetched first to check the arguments against the
+    /// parameters of its entrypoint, so mistakes are reported before invoking it.
     ///
     /// # Arguments
     ///
-    /// * `request` - The list requests request
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    /// * `args` - The entrypoint arguments, by parameter name
     ///
     /// # Returns
     ///
-    /// Returns the list of requests for the application.
+    /// Returns the invocation response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApplicationsError::InvalidRequest` if a required argument is missing
+    /// or an argument doesn't match any parameter.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::ListRequestsRequest}};
+    /// use std::collections::HashMap;
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
     ///
     /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
     ///         .bearer_token("your-api-key")
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
-    ///     let request = ListRequestsRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .limit(10)
-    ///         .build()?;
-    ///     apps_client.list_requests(&request).await?;
+    ///     let args = HashMap::from([("input_text".to_string(), serde_json::json!("hello"))]);
+    ///     let request_id = apps_client
+    ///         .invoke_with_args(&"default".into(), &"my-app".into(), args)
+    ///         .await?
+    ///         .into_request_id()?;
+    ///     println!("Request ID: {}", request_id);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn list_requests(
+    pub async fn invoke_with_args(
         &self,
-        request: &models::ListRequestsRequest,
-    ) -> Result<models::ApplicationRequests, SdkError> {
-        let uri_str = format!(
-            "/v1/namespaces/{}/applications/{}/requests",
-            request.namespace, request.application
-        );
-        let mut req_builder = self.client.request(Method::GET, &uri_str);
-
-        if let Some(ref param_value) = request.limit {
-            req_builder = req_builder.query(&[("limit", &param_value.to_string())]);
-        }
-        if let Some(ref param_value) = request.cursor {
-            req_builder = req_builder.query(&[("cursor", &param_value)]);
-        }
-        if let Some(ref param_value) = request.direction {
-            req_builder = req_builder.query(&[("direction", &param_value.to_string())]);
-        }
-
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
-
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let list = serde_path_to_error::deserialize(jd)?;
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        args: HashMap<String, serde_json::Value>,
+    ) -> Result<models::InvokeResponse, SdkError> {
+        let app = self
+            .get(&models::GetApplicationRequest {
+                namespace: namespace.clone(),
+                application: application.clone(),
+                headers: Vec::new(),
+                timeout: None,
+            })
+            .await?;
+        app.validate_invoke_args(&args)?;
 
-        Ok(list)
+        let request = models::InvokeApplicationRequest {
+            namespace: namespace.clone(),
+            application: application.clone(),
+            body: serde_json::Value::Object(args.into_iter().collect()),
+            headers: Vec::new(),
+            timeout: None,
+        };
+        self.invoke(&request).await
     }
 
-    /// Get details of a specific request.
+    /// Invoke an application with multiple inputs concurrently.
     ///
     /// # Arguments
     ///
-    /// * `request` - The get request request
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    /// * `bodies` - The inputs to invoke the application with, one invocation per input
+    /// * `concurrency` - The maximum number of invocations in flight at the same time
     ///
     /// # Returns
     ///
-    /// Returns the request details including all function runs and their allocations.
+    /// Returns the result of each invocation, in the same order as `bodies`.
+    /// A failed invocation doesn't stop the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `concurrency` is zero.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetRequestRequest}};
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
     ///
     /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
     ///         .bearer_token("your-api-key")
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
-    ///     let request = GetRequestRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .request_id("request-123")
-    ///         .build()?;
-    ///     let req_details = apps_client.get_request(&request).await?;
+    ///     let bodies = vec![
+    ///         serde_json::json!({"input": "hello"}),
+    ///         serde_json::json!({"input": "world"}),
+    ///     ];
+    ///     let results = apps_client
+    ///         .invoke_batch(&"default".into(), &"my-app".into(), bodies, 8)
+    ///         .await?;
+    ///     for request_id in results.successes() {
+    ///         println!("Request ID: {}", request_id);
+    ///     }
+    ///     for (index, e) in results.failures() {
+    ///         eprintln!("Input {index} failed: {}", e);
+    ///     }
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_request(
+    pub async fn invoke_batch(
         &self,
-        request: &models::GetRequestRequest,
-    ) -> Result<models::Request, SdkError> {
-        let uri_str = format!(
-            "/v1/namespaces/{}/applications/{}/requests/{}",
-            request.namespace, request.application, request.request_id
-        );
-        let mut req_builder = self.client.request(Method::GET, &uri_str);
-        if let Some(token) = &request.updates_pagination_token {
-            req_builder = req_builder.query(&["nextToken", token]);
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        bodies: Vec<serde_json::Value>,
+        concurrency: usize,
+    ) -> Result<BulkResult<models::RequestId>, SdkError> {
+        if concurrency == 0 {
+            return Err(ApplicationsError::InvalidRequest(
+                "concurrency must be greater than zero".to_string(),
+            )
+            .into());
         }
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        let invocations = bodies.into_iter().enumerate().map(|(index, body)| {
+            let request = models::InvokeApplicationRequest {
+                namespace: namespace.clone(),
+                application: application.clone(),
+                body,
+                headers: Vec::new(),
+                timeout: None,
+            };
+            async move {
+                let result = match self.invoke(&request).await {
+                    Ok(response) => response.into_request_id(),
+                    Err(error) => Err(error),
+                };
+                (index, result)
+            }
+        });
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let req_details = serde_path_to_error::deserialize(jd)?;
+        let mut results: Vec<_> = stream::iter(invocations)
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        results.sort_by_key(|(index, _)| *index);
 
-        Ok(req_details)
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
-    /// Delete a request.
+    /// List requests for an application.
     ///
     /// # Arguments
     ///
-    /// * `request` - The delete request request
+    /// * `request` - The list requests request
+    ///
+    /// # Returns
+    ///
+    /// Returns the list of requests for the application.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::DeleteRequestRequest}};
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::ListRequestsRequest}};
     ///
     /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
     ///         .bearer_token("your-api-key")
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
-    ///     let request = DeleteRequestRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .request_id("request-123")
+    ///     let request = ListRequestsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .limit(10)
     ///         .build()?;
-    ///     apps_client.delete_request(&request).await?;
+    ///     apps_client.list_requests(&request).await?;
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete_request(
+    pub async fn list_requests(
         &self,
-        request: &models::DeleteRequestRequest,
+        request: &models::ListRequestsRequest,
+    ) -> Result<models::ApplicationRequests, SdkError> {
+        with_timeout(request.timeout, self.list_requests_inner(request)).await
+    }
+
+    async fn list_requests_inner(
+        &self,
+        request: &models::ListRequestsRequest,
+    ) -> Result<models::ApplicationRequests, SdkError> {
+        let mut req = self.list_requests_request(request).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUESTS_ROUTE).await?;
+
+        let list = deserialize_json(resp).await?;
+
+        Ok(list)
+    }
+
+    /// Count the requests of an application.
+    ///
+    /// The total is read from a single page of requests. Nothing is paged through,
+    /// so the count is `None` when the server doesn't report a total.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::applications::ApplicationsClient;
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let count = apps_client
+    ///         .count_requests(&"default".into(), &"my-app".into())
+    ///         .await?;
+    ///     if let Some(count) = count {
+    ///         println!("{count} total requests");
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn count_requests(
+        &self,
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+    ) -> Result<Option<u64>, SdkError> {
+        let request = models::ListRequestsRequest::builder()
+            .namespace(namespace.clone())
+            .application(application.clone())
+            .limit(1)
+            .build()
+            .map_err(|error| ApplicationsError::InvalidRequest(error.to_string()))?;
+
+        let page = self.list_requests(&request).await?;
+        Ok(page.total)
+    }
+
+    /// List the requests of an application, deserializing them one at a time.
+    ///
+    /// Unlike [`ApplicationsClient::list_requests`], the response is never held in
+    /// memory as a whole, which matters for applications with many requests. The
+    /// pagination cursor of the response is skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The list requests request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the requests of the application.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::ListRequestsRequest};
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = ListRequestsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .limit(1000)
+    ///         .build()?;
+    ///     let mut requests = apps_client.list_requests_stream(&request).await?;
+    ///     while let Some(request) = requests.next().await {
+    ///         println!("{}", request?.id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn list_requests_stream(
+        &self,
+        request: &models::ListRequestsRequest,
+    ) -> Result<EventStream<models::ShallowRequest>, SdkError> {
+        with_timeout(request.timeout, self.list_requests_stream_inner(request)).await
+    }
+
+    async fn list_requests_stream_inner(
+        &self,
+        request: &models::ListRequestsRequest,
+    ) -> Result<EventStream<models::ShallowRequest>, SdkError> {
+        let mut req = self.list_requests_request(request).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUESTS_ROUTE).await?;
+
+        Ok(json_array_stream(resp, "requests"))
+    }
+
+    fn list_requests_request(
+        &self,
+        request: &models::ListRequestsRequest,
+    ) -> reqwest_middleware::RequestBuilder {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/requests",
+            urlencode(&request.namespace),
+            urlencode(&request.application)
+        );
+        let mut req_builder = self.client.request(Method::GET, &uri_str);
+
+        if let Some(ref param_value) = request.limit {
+            req_builder = req_builder.query(&[("limit", &param_value.to_string())]);
+        }
+        if let Some(ref param_value) = request.cursor {
+            req_builder = req_builder.query(&[("cursor", &param_value)]);
+        }
+        if let Some(ref param_value) = request.direction {
+            req_builder = req_builder.query(&[("direction", &param_value.to_string())]);
+        }
+        req_builder.query(&request.extra_query)
+    }
+
+    /// Get details of a specific request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The get request request
+    ///
+    /// # Returns
+    ///
+    /// Returns the request details including all function runs and their allocations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetRequestRequest}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let request = GetRequestRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .build()?;
+    ///     let req_details = apps_client.get_request(&request).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_request(
+        &self,
+        request: &models::GetRequestRequest,
+    ) -> Result<models::Request, SdkError> {
+        with_timeout(request.timeout, self.get_request_inner(request)).await
+    }
+
+    async fn get_request_inner(
+        &self,
+        request: &models::GetRequestRequest,
+    ) -> Result<models::Request, SdkError> {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/requests/{}",
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
+        );
+        let mut req_builder = self.client.request(Method::GET, &uri_str);
+        if let Some(token) = &request.updates_pagination_token {
+            req_builder = req_builder.query(&["nextToken", token]);
+        }
+
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUEST_ROUTE).await?;
+
+        let req_details = deserialize_json(resp).await?;
+
+        Ok(req_details)
+    }
+
+    /// Get a single function run of a request, including its allocations.
+    ///
+    /// This avoids fetching the whole request when only one function run is of interest.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The get function run request
+    ///
+    /// # Returns
+    ///
+    /// Returns the function run with its allocation history.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetFunctionRunRequest}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let request = GetFunctionRunRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .function_run_id("run-456".into())
+    ///         .build()?;
+    ///     let run = apps_client.get_function_run(&request).await?;
+    ///     println!("{} allocations", run.allocations.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_function_run(
+        &self,
+        request: &models::GetFunctionRunRequest,
+    ) -> Result<models::FunctionRun, SdkError> {
+        with_timeout(request.timeout, self.get_function_run_inner(request)).await
+    }
+
+    async fn get_function_run_inner(
+        &self,
+        request: &models::GetFunctionRunRequest,
+    ) -> Result<models::FunctionRun, SdkError> {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/requests/{}/function-runs/{}",
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id),
+            urlencode(&request.function_run_id)
+        );
+        let mut req = self.client.request(Method::GET, &uri_str).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, FUNCTION_RUN_ROUTE).await?;
+
+        let function_run = deserialize_json(resp).await?;
+
+        Ok(function_run)
+    }
+
+    /// Delete a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The delete request request
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::DeleteRequestRequest}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let request = DeleteRequestRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .build()?;
+    ///     apps_client.delete_request(&request).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_request(
+        &self,
+        request: &models::DeleteRequestRequest,
+    ) -> Result<(), SdkError> {
+        with_timeout(request.timeout, self.delete_request_inner(request)).await
+    }
+
+    async fn delete_request_inner(
+        &self,
+        request: &models::DeleteRequestRequest,
     ) -> Result<(), SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}/requests/{}",
-            request.namespace, request.application, request.request_id
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
         );
         let req_builder = self.client.request(Method::DELETE, &uri_str);
 
-        let req = req_builder.build()?;
-        let _resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let _resp = self.client.execute_route(req, REQUEST_ROUTE).await?;
 
         Ok(())
     }
@@ -502,10 +1199,10 @@ impl ApplicationsClient {
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
     ///     let request = DownloadFunctionOutputRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .request_id("request-123")
-    ///         .function_call_id("func-456")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .function_call_id("func-456".into())
     ///         .build()?;
     ///     apps_client.download_function_output(&request).await?;
     ///     Ok(())
@@ -514,27 +1211,38 @@ impl ApplicationsClient {
     pub async fn download_function_output(
         &self,
         request: &models::DownloadFunctionOutputRequest,
+    ) -> Result<models::DownloadOutput, SdkError> {
+        with_timeout(
+            request.timeout,
+            self.download_function_output_inner(request),
+        )
+        .await
+    }
+
+    async fn download_function_output_inner(
+        &self,
+        request: &models::DownloadFunctionOutputRequest,
     ) -> Result<models::DownloadOutput, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}/requests/{}/output/{}",
-            request.namespace, request.application, request.request_id, request.function_call_id
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id),
+            urlencode(&request.function_call_id)
         );
-        let req_builder = self.client.request(reqwest::Method::GET, &uri_str);
-
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
-
-        let mut output = models::DownloadOutput {
-            content_type: resp.headers().get(CONTENT_TYPE).cloned(),
-            content_length: resp.headers().get(CONTENT_LENGTH).cloned(),
-            content: Bytes::new(),
-        };
-
-        if resp.status().is_success() {
-            output.content = resp.bytes().await?;
+        let mut req_builder = self.client.request(reqwest::Method::GET, &uri_str);
+        if let Some(accept) = &request.accept {
+            req_builder = req_builder.header(ACCEPT, accept);
         }
 
-        Ok(output)
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self
+            .client
+            .execute_route(req, FUNCTION_OUTPUT_ROUTE)
+            .await?;
+
+        read_download_output(resp).await
     }
 
     /// Check if output is available for a request without downloading the content.
@@ -560,9 +1268,9 @@ impl ApplicationsClient {
     ///         .build()?;
     ///     let apps_client = ApplicationsClient::new(client);
     ///     let request = CheckFunctionOutputRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .request_id("request-123")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
     ///         .build()?;
     ///     if let Some(metadata) = apps_client.check_function_output(&request).await? {
     ///         println!("Output available, size: {:?}", metadata.content_length);
@@ -573,15 +1281,25 @@ impl ApplicationsClient {
     pub async fn check_function_output(
         &self,
         request: &models::CheckFunctionOutputRequest,
+    ) -> Result<Option<models::DownloadOutput>, SdkError> {
+        with_timeout(request.timeout, self.check_function_output_inner(request)).await
+    }
+
+    async fn check_function_output_inner(
+        &self,
+        request: &models::CheckFunctionOutputRequest,
     ) -> Result<Option<models::DownloadOutput>, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}/requests/{}/output",
-            request.namespace, request.application, request.request_id
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
         );
         let req_builder = self.client.request(Method::HEAD, &uri_str);
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUEST_OUTPUT_ROUTE).await?;
 
         if resp.status() == StatusCode::NO_CONTENT {
             return Ok(None);
@@ -611,86 +1329,354 @@ impl ApplicationsClient {
     ///
     /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
     ///     let request = DownloadRequestOutputRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .request_id("request-123")
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .accept("application/json")
     ///         .build()?;
     ///     let output = apps_client.download_request_output(&request).await?;
+    ///     println!("Content type: {:?}", output.content_type);
     ///     Ok(())
     /// }
     /// ```
     pub async fn download_request_output(
         &self,
         request: &models::DownloadRequestOutputRequest,
+    ) -> Result<models::DownloadOutput, SdkError> {
+        with_timeout(request.timeout, self.download_request_output_inner(request)).await
+    }
+
+    async fn download_request_output_inner(
+        &self,
+        request: &models::DownloadRequestOutputRequest,
     ) -> Result<models::DownloadOutput, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}/requests/{}/output",
-            request.namespace, request.application, request.request_id
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
         );
-        let req_builder = self.client.request(Method::GET, &uri_str);
-
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
-
-        let mut output = models::DownloadOutput {
-            content_type: resp.headers().get(CONTENT_TYPE).cloned(),
-            content_length: resp.headers().get(CONTENT_LENGTH).cloned(),
-            content: Bytes::new(),
-        };
-
-        if resp.status().is_success() {
-            output.content = resp.bytes().await?;
+        let mut req_builder = self.client.request(Method::GET, &uri_str);
+        if let Some(accept) = &request.accept {
+            req_builder = req_builder.header(ACCEPT, accept);
         }
 
-        Ok(output)
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUEST_OUTPUT_ROUTE).await?;
+
+        read_download_output(resp).await
     }
 
-    /// Get logs for an application.
+    /// Wait for the output of a request to be available, then download it.
+    ///
+    /// The output of a finished request can take a moment to be stored. This
+    /// checks for it with [`ApplicationsClient::check_function_output`] every
+    /// `poll_interval` until it exists, then downloads it with
+    /// [`ApplicationsClient::download_request_output`]. Checks answered with
+    /// `404 Not Found` are retried.
+    ///
+    /// While the output is missing, the request itself is checked: once it
+    /// finished without output, the output is empty, or the request's failure is
+    /// returned.
     ///
     /// # Arguments
     ///
-    /// * `request` - The get logs request
+    /// * `request` - The download request output request
+    /// * `poll_interval` - How long to wait between checks
+    /// * `timeout` - How long to wait for the output, including its download
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// Returns the logs for the application matching the request filters.
+    /// Returns [`SdkError::Timeout`] if the output isn't downloaded within `timeout`,
+    /// or [`SdkError::RequestFailed`] if the request failed without output.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetLogsRequest}};
+    /// use std::time::Duration;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::DownloadRequestOutputRequest};
     ///
-    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
-    ///         .bearer_token("your-api-key")
-    ///         .build()?;
-    ///     let apps_client = ApplicationsClient::new(client);
-    ///     let request = GetLogsRequest::builder()
-    ///         .namespace("default")
-    ///         .application("my-app")
-    ///         .tail(100)
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = DownloadRequestOutputRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
     ///         .build()?;
-    ///     apps_client.get_logs(&request).await?;
+    ///     let output = apps_client
+    ///         .download_request_output_when_ready(&request, Duration::from_secs(1), Duration::from_secs(60))
+    ///         .await?;
+    ///     println!("Content type: {:?}", output.content_type);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn get_logs(
+    pub async fn download_request_output_when_ready(
         &self,
-        request: &models::GetLogsRequest,
-    ) -> Result<models::EventsResponse, SdkError> {
-        let uri_str = format!(
-            "/v1/namespaces/{}/applications/{}/logs",
-            request.namespace, request.application
-        );
-        let mut req_builder = self.client.request(Method::GET, &uri_str);
+        request: &models::DownloadRequestOutputRequest,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<models::DownloadOutput, SdkError> {
+        with_timeout(
+            Some(timeout),
+            self.download_request_output_when_ready_inner(request, poll_interval),
+        )
+        .await
+    }
 
-        if let Some(ref param_value) = request.request_id {
-            req_builder = req_builder.query(&[("requestId", param_value)]);
-        }
-        if let Some(ref param_value) = request.container_id {
-            req_builder = req_builder.query(&[("containerId", param_value)]);
-        }
-        if let Some(ref param_value) = request.function {
+    async fn download_request_output_when_ready_inner(
+        &self,
+        request: &models::DownloadRequestOutputRequest,
+        poll_interval: Duration,
+    ) -> Result<models::DownloadOutput, SdkError> {
+        let check = models::CheckFunctionOutputRequest {
+            namespace: request.namespace.clone(),
+            application: request.application.clone(),
+            request_id: request.request_id.clone(),
+            headers: request.headers.clone(),
+            timeout: None,
+        };
+        let get = models::GetRequestRequest {
+            namespace: request.namespace.clone(),
+            application: request.application.clone(),
+            request_id: request.request_id.clone(),
+            updates_pagination_token: None,
+            headers: request.headers.clone(),
+            timeout: None,
+        };
+        loop {
+            match self.check_function_output(&check).await {
+                Ok(Some(_)) => return self.download_request_output(request).await,
+                Ok(None) => {
+                    // A finished request without output doesn't get one later.
+                    let details = self.get_request(&get).await?;
+                    if details.is_complete() {
+                        return match details.into_error() {
+                            Some(error) => Err(error),
+                            None => Ok(models::DownloadOutput {
+                                content_type: None,
+                                content_length: None,
+                                content: Bytes::new(),
+                            }),
+                        };
+                    }
+                }
+                // The output isn't stored yet.
+                Err(SdkError::ServerError {
+                    status: StatusCode::NOT_FOUND,
+                    ..
+                }) => {}
+                Err(error) => return Err(error),
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
+    }
+
+    /// Stream the complete output of a request, without buffering it in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The download request output request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of output chunks. The stream ends with an
+    /// `SdkError::UnexpectedResponse` error if the number of bytes received
+    /// doesn't match the `Content-Length` announced by the server.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::DownloadRequestOutputRequest};
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = DownloadRequestOutputRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .build()?;
+    ///     let mut output = apps_client.stream_request_output(&request).await?;
+    ///     while let Some(chunk) = output.next().await {
+    ///         println!("Received {} bytes", chunk?.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stream_request_output(
+        &self,
+        request: &models::DownloadRequestOutputRequest,
+    ) -> Result<models::DownloadStream, SdkError> {
+        with_timeout(request.timeout, self.stream_request_output_inner(request)).await
+    }
+
+    async fn stream_request_output_inner(
+        &self,
+        request: &models::DownloadRequestOutputRequest,
+    ) -> Result<models::DownloadStream, SdkError> {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/requests/{}/output",
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
+        );
+        let mut req_builder = self.client.request(Method::GET, &uri_str);
+        if let Some(accept) = &request.accept {
+            req_builder = req_builder.header(ACCEPT, accept);
+        }
+
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, REQUEST_OUTPUT_ROUTE).await?;
+
+        if !content_encodings(&resp)?.is_empty() {
+            return Err(SdkError::UnexpectedResponse {
+                detail: "compressed outputs can't be streamed, download them instead".to_string(),
+            });
+        }
+        let content_type = resp.headers().get(CONTENT_TYPE).cloned();
+        let content_length = resp.content_length();
+        let stream = resp
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(SdkError::from));
+
+        Ok(models::DownloadStream::new(
+            content_type,
+            content_length,
+            stream,
+        ))
+    }
+
+    /// Get logs for an application.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The get logs request
+    ///
+    /// # Returns
+    ///
+    /// Returns the logs for the application matching the request filters.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetLogsRequest}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let request = GetLogsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .tail(100)
+    ///         .build()?;
+    ///     apps_client.get_logs(&request).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_logs(
+        &self,
+        request: &models::GetLogsRequest,
+    ) -> Result<models::EventsResponse, SdkError> {
+        with_timeout(request.timeout, self.get_logs_inner(request)).await
+    }
+
+    async fn get_logs_inner(
+        &self,
+        request: &models::GetLogsRequest,
+    ) -> Result<models::EventsResponse, SdkError> {
+        let mut req = self.logs_request(request).build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, LOGS_ROUTE).await?;
+
+        let events_resp = deserialize_json(resp).await?;
+
+        Ok(events_resp)
+    }
+
+    /// Stream logs for an application as newline-delimited JSON.
+    ///
+    /// Unlike [`ApplicationsClient::get_logs`], the logs are decoded as they
+    /// arrive instead of after the whole response has been received. Servers
+    /// that answer with a JSON document instead are read the same way, skipping
+    /// its `next_token`.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The get logs request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the logs matching the request filters.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::GetLogsRequest}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let request = GetLogsRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .build()?;
+    ///     let mut logs = apps_client.stream_logs(&request).await?;
+    ///     while let Some(log) = logs.next().await {
+    ///         println!("{}", log?.body);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stream_logs(
+        &self,
+        request: &models::GetLogsRequest,
+    ) -> Result<EventStream<models::LogSignal>, SdkError> {
+        with_timeout(request.timeout, self.stream_logs_inner(request)).await
+    }
+
+    async fn stream_logs_inner(
+        &self,
+        request: &models::GetLogsRequest,
+    ) -> Result<EventStream<models::LogSignal>, SdkError> {
+        let mut req = self
+            .logs_request(request)
+            .header(ACCEPT, "application/x-ndjson")
+            .build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, LOGS_ROUTE).await?;
+
+        // Servers that don't stream logs answer with the JSON body of `get_logs`.
+        let is_json = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(is_json_content_type);
+        if is_json {
+            return Ok(json_array_stream(resp, "logs"));
+        }
+        Ok(ndjson_stream(resp))
+    }
+
+    fn logs_request(&self, request: &models::GetLogsRequest) -> reqwest_middleware::RequestBuilder {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/logs",
+            urlencode(&request.namespace),
+            urlencode(&request.application)
+        );
+        let mut req_builder = self.client.request(Method::GET, &uri_str);
+
+        if let Some(ref param_value) = request.request_id {
+            req_builder = req_builder.query(&[("requestId", param_value)]);
+        }
+        if let Some(ref param_value) = request.container_id {
+            req_builder = req_builder.query(&[("containerId", param_value)]);
+        }
+        if let Some(ref param_value) = request.function {
             req_builder = req_builder.query(&[("function", param_value)]);
         }
         if let Some(ref param_value) = request.next_token {
@@ -709,14 +1695,481 @@ impl ApplicationsClient {
             req_builder = req_builder.query(&[("functionExecutor", param_value)]);
         }
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        req_builder
+    }
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let events_resp = serde_path_to_error::deserialize(jd)?;
+    /// Get the most recent log lines across all the functions of an application.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    /// * `lines` - The maximum number of log lines to return
+    ///
+    /// # Returns
+    ///
+    /// Returns up to `lines` log signals, sorted by timestamp from oldest to newest.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::ApplicationsClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     for log in apps_client
+    ///         .tail_logs(&"default".into(), &"my-app".into(), 50)
+    ///         .await?
+    ///     {
+    ///         println!("{}", log.body);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn tail_logs(
+        &self,
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        lines: usize,
+    ) -> Result<Vec<models::LogSignal>, SdkError> {
+        let request = models::GetLogsRequest {
+            namespace: namespace.clone(),
+            application: application.clone(),
+            request_id: None,
+            container_id: None,
+            function: None,
+            next_token: None,
+            head: None,
+            tail: Some(lines),
+            ignore: None,
+            function_executor: None,
+            headers: Vec::new(),
+            timeout: None,
+        };
 
-        Ok(events_resp)
+        let mut logs = self.get_logs(&request).await?.logs;
+        logs.sort_by_key(|log| log.timestamp);
+        if logs.len() > lines {
+            logs.drain(..logs.len() - lines);
+        }
+
+        Ok(logs)
+    }
+
+    /// Stream the progress updates of a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The stream progress request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of progress events. When `from_token` is set, the stream
+    /// starts right after the update identified by the token, so updates already
+    /// read with [`ApplicationsClient::get_progress_updates`] are not repeated.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{
+    ///     ApplicationsClient,
+    ///     models::{ProgressUpdatesRequest, ProgressUpdatesResponse, StreamProgressRequest},
+    /// };
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let history = ProgressUpdatesRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .request_id("request-123".into())
+    ///         .paginated()
+    ///         .build()?;
+    ///     let ProgressUpdatesResponse::Json(page) = apps_client.get_progress_updates(&history).await? else {
+    ///         return Ok(());
+    ///     };
+    ///
+    ///     let mut request = StreamProgressRequest::builder();
+    ///     request.namespace("default".into()).application("my-app".into()).request_id("request-123".into());
+    ///     if let Some(token) = page.next_token {
+    ///         request.from_token(token);
+    ///     }
+    ///     let mut stream = apps_client.stream_progress(&request.build()?).await?;
+    ///     while let Some(event) = stream.next().await {
+    ///         println!("{:?}", event?);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn stream_progress(
+        &self,
+        request: &models::StreamProgressRequest,
+    ) -> Result<models::ProgressUpdatesStream, SdkError> {
+        let uri_str = format!(
+            "/v1/namespaces/{}/applications/{}/requests/{}/updates",
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
+        );
+        let query: Vec<_> = request
+            .from_token
+            .iter()
+            .map(|token| ("nextToken", token.as_str()))
+            .collect();
+
+        let stream = self
+            .client
+            .build_event_source_request_with_query::<RequestStateChangeEvent>(&uri_str, &query)
+            .await?;
+
+        Ok(stream)
+    }
+
+    /// Invoke an application and stream the progress of the resulting request.
+    ///
+    /// The output of the request is downloaded as soon as the stream reports that
+    /// the request succeeded, and is then available from [`InvokeStream::final_output`].
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The invoke application request
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the progress events of the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::InvokeApplicationRequest};
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = InvokeApplicationRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .body(serde_json::json!({"input": "hello world"}))
+    ///         .build()?;
+    ///     let mut stream = apps_client.invoke_and_stream(&request).await?;
+    ///     while let Some(event) = stream.next().await {
+    ///         println!("{}", event?.as_str());
+    ///     }
+    ///     if let Some(output) = stream.final_output().await? {
+    ///         println!("Output: {} bytes", output.content.len());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn invoke_and_stream(
+        &self,
+        request: &models::InvokeApplicationRequest,
+    ) -> Result<InvokeStream, SdkError> {
+        let request_id: models::RequestId = self.invoke(request).await?.into_request_id()?;
+        let events = self
+            .stream_progress(&models::StreamProgressRequest {
+                namespace: request.namespace.clone(),
+                application: request.application.clone(),
+                request_id: request_id.clone(),
+                from_token: None,
+            })
+            .await?;
+
+        Ok(InvokeStream {
+            client: self.clone(),
+            namespace: request.namespace.clone(),
+            application: request.application.clone(),
+            request_id,
+            events,
+            output: None,
+        })
+    }
+
+    /// Invoke an application and stream the logs of the resulting request.
+    ///
+    /// The logs are polled, following their pagination token, until the request
+    /// finishes. Polls that return no new logs are spaced out following the
+    /// default [`BackoffPolicy`](models::BackoffPolicy).
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The invoke application request
+    ///
+    /// # Returns
+    ///
+    /// Returns the ID of the request, and a stream of the logs of the request as
+    /// they arrive. The stream ends once the request has finished and its last
+    /// logs have been read, or after the first error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::applications::{ApplicationsClient, models::InvokeApplicationRequest};
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let request = InvokeApplicationRequest::builder()
+    ///         .namespace("default".into())
+    ///         .application("my-app".into())
+    ///         .body(serde_json::json!({"input": "hello world"}))
+    ///         .build()?;
+    ///     let (request_id, mut logs) = apps_client.invoke_and_tail_logs(&request).await?;
+    ///     println!("Request: {request_id}");
+    ///     while let Some(log) = logs.next().await {
+    ///         println!("{}", log?.body);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn invoke_and_tail_logs(
+        &self,
+        request: &models::InvokeApplicationRequest,
+    ) -> Result<(models::RequestId, EventStream<models::LogSignal>), SdkError> {
+        struct TailState {
+            logs: models::GetLogsRequest,
+            status: models::GetRequestRequest,
+            delay: Option<Duration>,
+            pending: VecDeque<models::LogSignal>,
+            seen: HashSet<Uuid>,
+            request_finished: bool,
+            finished: bool,
+        }
+
+        let request_id: models::RequestId = self.invoke(request).await?.into_request_id()?;
+        let state = TailState {
+            logs: models::GetLogsRequest {
+                namespace: request.namespace.clone(),
+                application: request.application.clone(),
+                request_id: Some(request_id.clone()),
+                container_id: None,
+                function: None,
+                next_token: None,
+                head: None,
+                tail: None,
+                ignore: None,
+                function_executor: None,
+                headers: request.headers.clone(),
+                timeout: None,
+            },
+            status: models::GetRequestRequest {
+                namespace: request.namespace.clone(),
+                application: request.application.clone(),
+                request_id: request_id.clone(),
+                updates_pagination_token: None,
+                headers: request.headers.clone(),
+                timeout: None,
+            },
+            delay: None,
+            pending: VecDeque::new(),
+            seen: HashSet::new(),
+            request_finished: false,
+            finished: false,
+        };
+        let client = self.clone();
+        let backoff = models::BackoffPolicy::default();
+
+        let logs = stream::try_unfold(state, move |mut state| {
+            let client = client.clone();
+            let backoff = backoff.clone();
+            async move {
+                loop {
+                    if let Some(log) = state.pending.pop_front() {
+                        return Ok(Some((log, state)));
+                    }
+                    if state.finished {
+                        return Ok(None);
+                    }
+                    if let Some(delay) = state.delay {
+                        tokio::time::sleep(delay).await;
+                    }
+
+                    let page = client.get_logs(&state.logs).await?;
+                    if let Some(token) = page.next_token {
+                        state.logs.next_token = Some(token);
+                    }
+                    // Without a new token, the same logs are returned again.
+                    let new_logs = page
+                        .logs
+                        .into_iter()
+                        .filter(|log| state.seen.insert(log.uuid))
+                        .collect::<Vec<_>>();
+                    if !new_logs.is_empty() {
+                        state.delay = None;
+                        state.pending.extend(new_logs);
+                        continue;
+                    }
+                    if state.request_finished {
+                        state.finished = true;
+                        continue;
+                    }
+
+                    // The logs written before the request finished are read by one
+                    // more poll once it has.
+                    state.request_finished = client.get_request(&state.status).await?.is_complete();
+                    state.delay = if state.request_finished {
+                        None
+                    } else {
+                        Some(backoff.next_delay(state.delay))
+                    };
+                }
+            }
+        });
+
+        Ok((request_id, EventStream::new(logs)))
+    }
+
+    /// Watch the progress of a request by polling its paginated progress updates.
+    ///
+    /// Polls are repeated right away while they return new updates, and are
+    /// spaced out following `backoff` while they don't. The stream ends after
+    /// the request finishes, or after the first error.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    /// * `request_id` - The ID of the request to watch
+    /// * `backoff` - The delays between polls without new updates
+    ///
+    /// # Returns
+    ///
+    /// Returns a stream of the progress events of the request.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures::StreamExt;
+    /// use tensorlake_cloud_sdk::{ClientBuilder, applications::{ApplicationsClient, models::BackoffPolicy}};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let apps_client = ApplicationsClient::new(client);
+    ///     let mut events = apps_client.watch_progress(
+    ///         &"default".into(),
+    ///         &"my-app".into(),
+    ///         &"request-123".into(),
+    ///         BackoffPolicy::default(),
+    ///     );
+    ///     while let Some(event) = events.next().await {
+    ///         println!("{}", event?.as_str());
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn watch_progress(
+        &self,
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+        backoff: models::BackoffPolicy,
+    ) -> models::ProgressUpdatesStream {
+        struct WatchState {
+            pages: ProgressPages,
+            delay: Option<Duration>,
+            pending: VecDeque<RequestStateChangeEvent>,
+            finished: bool,
+        }
+
+        let pages = match ProgressPages::new(namespace, application, request_id) {
+            Ok(pages) => pages,
+            Err(error) => return EventStream::new(stream::once(async move { Err(error) })),
+        };
+        let state = WatchState {
+            pages,
+            delay: None,
+            pending: VecDeque::new(),
+            finished: false,
+        };
+        let client = self.clone();
+
+        let events = stream::try_unfold(state, move |mut state| {
+            let client = client.clone();
+            let backoff = backoff.clone();
+            async move {
+                loop {
+                    if let Some(event) = state.pending.pop_front() {
+                        if event.is_terminal() {
+                            state.finished = true;
+                            state.pending.clear();
+                        }
+                        return Ok(Some((event, state)));
+                    }
+                    if state.finished {
+                        return Ok(None);
+                    }
+                    if let Some(delay) = state.delay {
+                        tokio::time::sleep(delay).await;
+                    }
+
+                    let (new_updates, _) = state.pages.next(&client).await?;
+                    state.delay = if new_updates.is_empty() {
+                        Some(backoff.next_delay(state.delay))
+                    } else {
+                        None
+                    };
+                    state.pending.extend(new_updates);
+                }
+            }
+        });
+
+        EventStream::new(events)
+    }
+
+    /// Collect the progress events of a request into a [`RequestTimeline`](models::RequestTimeline).
+    ///
+    /// This is the batch counterpart of [`ApplicationsClient::watch_progress`], meant to
+    /// inspect a request after it finished. The history of the request is paged through
+    /// until the `RequestFinished` event, or until the server has no more events for a
+    /// request that is still running.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The namespace of the application
+    /// * `application` - The name of the application
+    /// * `request_id` - The ID of the request
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::applications::ApplicationsClient;
+    ///
+    /// async fn example(apps_client: &ApplicationsClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let timeline = apps_client
+    ///         .request_timeline(&"default".into(), &"my-app".into(), &"request-123".into())
+    ///         .await?;
+    ///     println!("{:?} -> {:?}", timeline.started_at(), timeline.finished_at());
+    ///     println!("{} events for `main`", timeline.function_events("main").count());
+    ///     println!("outcome: {:?}", timeline.final_outcome());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn request_timeline(
+        &self,
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+    ) -> Result<models::RequestTimeline, SdkError> {
+        let mut pages = ProgressPages::new(namespace, application, request_id)?;
+
+        let mut timeline = models::RequestTimeline::default();
+        loop {
+            let (updates, advanced) = pages.next(self).await?;
+            for event in updates {
+                let terminal = event.is_terminal();
+                timeline.events.push(event);
+                if terminal {
+                    return Ok(timeline);
+                }
+            }
+
+            // Without a new token the server has no more events for the request yet.
+            if !advanced {
+                return Ok(timeline);
+            }
+        }
     }
 
     pub async fn get_progress_updates(
@@ -725,7 +2178,9 @@ impl ApplicationsClient {
     ) -> Result<models::ProgressUpdatesResponse, SdkError> {
         let uri_str = format!(
             "/v1/namespaces/{}/applications/{}/requests/{}/updates",
-            request.namespace, request.application, request.request_id
+            urlencode(&request.namespace),
+            urlencode(&request.application),
+            urlencode(&request.request_id)
         );
 
         match request.mode {
@@ -744,13 +2199,897 @@ impl ApplicationsClient {
                 let req = self
                     .client
                     .build_get_json_request(&uri_str, query.as_deref())?;
-                let resp = self.client.execute(req).await?;
+                let resp = self
+                    .client
+                    .execute_route(req, PROGRESS_UPDATES_ROUTE)
+                    .await?;
 
-                let bytes = resp.bytes().await?;
-                let jd = &mut serde_json::Deserializer::from_slice(&bytes);
-                let response: models::ProgressUpdatesJson = serde_path_to_error::deserialize(jd)?;
+                let response: models::ProgressUpdatesJson = deserialize_json(resp).await?;
                 Ok(models::ProgressUpdatesResponse::Json(response))
             }
         }
     }
 }
+
+/// Pages through the paginated progress updates of a request.
+struct ProgressPages {
+    request: models::ProgressUpdatesRequest,
+    // Updates already received with the current token, which the server
+    // returns again until it hands out a new token.
+    received: usize,
+}
+
+impl ProgressPages {
+    fn new(
+        namespace: &models::Namespace,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+    ) -> Result<Self, SdkError> {
+        let request = models::ProgressUpdatesRequest::builder()
+            .namespace(namespace.clone())
+            .application(application.clone())
+            .request_id(request_id.clone())
+            .paginated()
+            .build()
+            .map_err(|error| ApplicationsError::InvalidRequest(error.to_string()))?;
+        Ok(Self {
+            request,
+            received: 0,
+        })
+    }
+
+    /// Fetch the updates not received yet, and whether the server handed out a
+    /// new token for the next page.
+    async fn next(
+        &mut self,
+        client: &ApplicationsClient,
+    ) -> Result<(Vec<RequestStateChangeEvent>, bool), SdkError> {
+        let models::ProgressUpdatesResponse::Json(updates) =
+            client.get_progress_updates(&self.request).await?
+        else {
+            return Err(SdkError::UnexpectedResponse {
+                detail: "paginated progress updates were not returned as JSON".to_string(),
+            });
+        };
+        let new_updates: Vec<_> = updates.updates.into_iter().skip(self.received).collect();
+        match (updates.next_token, &self.request.mode) {
+            (Some(token), models::ProgressUpdatesRequestMode::Paginated(current))
+                if current.as_ref() != Some(&token) =>
+            {
+                self.request.mode = models::ProgressUpdatesRequestMode::Paginated(Some(token));
+                self.received = 0;
+                Ok((new_updates, true))
+            }
+            _ => {
+                self.received += new_updates.len();
+                Ok((new_updates, false))
+            }
+        }
+    }
+}
+
+/// Read a downloaded output, decoding its `Content-Encoding`.
+///
+/// The content length of a decoded output is the length of the decoded content.
+async fn read_download_output(resp: reqwest::Response) -> Result<models::DownloadOutput, SdkError> {
+    let mut output = models::DownloadOutput {
+        content_type: resp.headers().get(CONTENT_TYPE).cloned(),
+        content_length: resp.headers().get(CONTENT_LENGTH).cloned(),
+        content: Bytes::new(),
+    };
+
+    if resp.status().is_success() {
+        let (content, decoded) = read_decoded_body(resp).await?;
+        if decoded {
+            output.content_length = Some(HeaderValue::from(content.len()));
+        }
+        output.content = content;
+    }
+
+    Ok(output)
+}
+
+/// The progress events of an invocation started with
+/// [`ApplicationsClient::invoke_and_stream`].
+///
+/// When the stream yields a successful `RequestFinished` event, the output of
+/// the request starts downloading, and the download makes progress while the
+/// stream is polled.
+pub struct InvokeStream {
+    client: ApplicationsClient,
+    namespace: models::Namespace,
+    application: models::ApplicationName,
+    request_id: models::RequestId,
+    events: models::ProgressUpdatesStream,
+    output: Option<OutputDownload>,
+}
+
+/// The download of the output of an [`InvokeStream`]'s request.
+enum OutputDownload {
+    Pending(BoxFuture<'static, Result<models::DownloadOutput, SdkError>>),
+    Done(Result<models::DownloadOutput, SdkError>),
+}
+
+impl InvokeStream {
+    /// The ID of the request created by the invocation.
+    pub fn request_id(&self) -> &models::RequestId {
+        &self.request_id
+    }
+
+    /// The output of the request.
+    ///
+    /// Any remaining events are drained first, so this waits for the request
+    /// to finish.
+    ///
+    /// # Returns
+    ///
+    /// Returns the output of the request, or `None` if the request didn't succeed
+    /// or the stream ended before the request finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the remaining events or the output can't be fetched.
+    pub async fn final_output(&mut self) -> Result<Option<models::DownloadOutput>, SdkError> {
+        while let Some(event) = self.next().await {
+            event?;
+        }
+
+        match self.output.take() {
+            Some(OutputDownload::Pending(download)) => download.await.map(Some),
+            Some(OutputDownload::Done(output)) => output.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn start_download(&mut self) {
+        let client = self.client.clone();
+        let request = models::DownloadRequestOutputRequest {
+            namespace: self.namespace.clone(),
+            application: self.application.clone(),
+            request_id: self.request_id.clone(),
+            accept: None,
+            headers: Vec::new(),
+            timeout: None,
+        };
+        self.output = Some(OutputDownload::Pending(
+            async move { client.download_request_output(&request).await }.boxed(),
+        ));
+    }
+}
+
+impl Stream for InvokeStream {
+    type Item = Result<RequestStateChangeEvent, SdkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(OutputDownload::Pending(download)) = &mut self.output
+            && let Poll::Ready(output) = download.poll_unpin(cx)
+        {
+            self.output = Some(OutputDownload::Done(output));
+        }
+
+        let event = ready!(self.events.poll_next_unpin(cx));
+        if let Some(Ok(RequestStateChangeEvent::RequestFinished(finished))) = &event
+            && finished.outcome == models::RequestOutcome::Success
+            && self.output.is_none()
+        {
+            self.start_download();
+        }
+        Poll::Ready(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientBuilder;
+    use crate::test_support::*;
+
+    #[tokio::test]
+    async fn test_invoke_and_stream_downloads_final_output() {
+        let server = serve_bodies(vec![
+            ("application/json", r#"{"request_id":"request-123"}"#),
+            (
+                "text/event-stream",
+                "data: {\"RequestFinished\":{\"namespace\":\"default\",\"application_name\":\"my-app\",\"application_version\":\"1\",\"request_id\":\"request-123\",\"outcome\":\"success\"}}\n\n",
+            ),
+            ("application/json", r#"{"answer":42}"#),
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body(serde_json::json!({}))
+            .build()
+            .unwrap();
+        let mut stream = apps_client.invoke_and_stream(&request).await.unwrap();
+        assert_eq!(stream.request_id(), "request-123");
+        let event = stream.next().await.unwrap().unwrap();
+        assert!(event.is_terminal());
+
+        let output = stream.final_output().await.unwrap().unwrap();
+        assert_eq!(output.content, Bytes::from_static(br#"{"answer":42}"#));
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "POST /v1/namespaces/default/applications/my-app HTTP/1.1",
+                "GET /v1/namespaces/default/applications/my-app/requests/request-123/updates HTTP/1.1",
+                "GET /v1/namespaces/default/applications/my-app/requests/request-123/output HTTP/1.1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_namespaced_client_uses_bound_namespace() {
+        let server = serve_json(vec![
+            r#"{"request_id":"request-123"}"#,
+            r#"{"requests":[]}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap())
+            .for_namespace(&"team a".into())
+            .unwrap();
+        assert_eq!(apps_client.namespace(), "team a");
+
+        let request_id = apps_client
+            .invoke(&"my-app".into(), serde_json::json!({}))
+            .await
+            .unwrap()
+            .into_request_id()
+            .unwrap();
+        assert_eq!(request_id, "request-123");
+        apps_client.list_requests(&"my-app".into()).await.unwrap();
+
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "POST /v1/namespaces/team%20a/applications/my-app HTTP/1.1",
+                "GET /v1/namespaces/team%20a/applications/my-app/requests HTTP/1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_namespaced_client_rejects_blank_namespace() {
+        let apps_client =
+            ApplicationsClient::new(ClientBuilder::new("http://localhost").build().unwrap());
+
+        let result = apps_client.for_namespace(&" ".into());
+        assert!(matches!(
+            result,
+            Err(SdkError::Applications(ApplicationsError::InvalidRequest(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_request_headers_are_sent() {
+        let server = serve_json(vec![r#"{"applications":[],"cursor":null}"#]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::ListApplicationsRequest::builder()
+            .namespace("default".into())
+            .headers(vec![("X-Trace-Id".to_string(), "trace-123".to_string())])
+            .build()
+            .unwrap();
+        apps_client.list(&request).await.unwrap();
+        assert!(
+            server.requests()[0]
+                .to_lowercase()
+                .contains("\r\nx-trace-id: trace-123\r\n")
+        );
+
+        let request = models::ListApplicationsRequest::builder()
+            .namespace("default".into())
+            .headers(vec![("bad header".to_string(), "value".to_string())])
+            .build()
+            .unwrap();
+        assert!(matches!(
+            apps_client.list(&request).await,
+            Err(SdkError::InvalidHeaderValue(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_expected_version() {
+        let server = serve(vec![response("412 Precondition Failed", &[], "")]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::UpsertApplicationRequest::builder()
+            .namespace("default".into())
+            .application_manifest(models::ApplicationManifest {
+                name: "my-app".to_string(),
+                version: "1".to_string(),
+                ..Default::default()
+            })
+            .code_zip(b"zip".to_vec())
+            .expected_version("3")
+            .build()
+            .unwrap();
+        let result = apps_client.upsert(&request).await;
+        assert!(
+            matches!(result, Err(SdkError::PreconditionFailed(_))),
+            "unexpected result: {result:?}"
+        );
+        assert!(
+            server.requests()[0]
+                .to_lowercase()
+                .contains("\r\nif-match: \"3\"\r\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upsert_from_reader_streams_code_zip() {
+        let server = serve(vec![response("200 OK", &[], "")]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::UpsertApplicationFromReaderRequest::builder()
+            .namespace("default".into())
+            .application_manifest(models::ApplicationManifest {
+                name: "my-app".to_string(),
+                version: "1".to_string(),
+                ..Default::default()
+            })
+            .build()
+            .unwrap();
+        // Upserts without a reader still need the code zip.
+        assert!(
+            models::UpsertApplicationRequest::builder()
+                .namespace("default".into())
+                .application_manifest(request.application_manifest.clone())
+                .build()
+                .is_err()
+        );
+
+        let code_zip = std::io::Cursor::new(b"zip bytes from a reader".to_vec());
+        apps_client
+            .upsert_from_reader(&request, code_zip)
+            .await
+            .unwrap();
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with("POST /v1/namespaces/default/applications HTTP/1.1"));
+        assert!(request.contains("filename=\"code.zip\""));
+        assert!(request.contains("zip bytes from a reader"));
+    }
+
+    #[tokio::test]
+    async fn test_download_request_output_when_ready() {
+        let running =
+            r#"{"id":"request-123","application_version":"1","created_at":1,"function_runs":[]}"#;
+        let server = serve(vec![
+            b"HTTP/1.1 404 Not Found\r\nConnection: close\r\n\r\n".to_vec(),
+            b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n".to_vec(),
+            ok("application/json", running),
+            b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 13\r\nConnection: close\r\n\r\n".to_vec(),
+            ok("application/json", r#"{"answer":42}"#),
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::DownloadRequestOutputRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .build()
+            .unwrap();
+        let output = apps_client
+            .download_request_output_when_ready(
+                &request,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.content, Bytes::from_static(br#"{"answer":42}"#));
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "HEAD /v1/namespaces/default/applications/my-app/requests/request-123/output HTTP/1.1",
+                "HEAD /v1/namespaces/default/applications/my-app/requests/request-123/output HTTP/1.1",
+                "GET /v1/namespaces/default/applications/my-app/requests/request-123 HTTP/1.1",
+                "HEAD /v1/namespaces/default/applications/my-app/requests/request-123/output HTTP/1.1",
+                "GET /v1/namespaces/default/applications/my-app/requests/request-123/output HTTP/1.1",
+            ]
+        );
+
+        // A request that finished without output has an empty output.
+        let finished = r#"{"id":"request-123","outcome":"success","application_version":"1","created_at":1,"function_runs":[]}"#;
+        let server = serve(vec![
+            b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n".to_vec(),
+            ok("application/json", finished),
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let output = apps_client
+            .download_request_output_when_ready(
+                &request,
+                Duration::from_millis(10),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert!(output.content.is_empty());
+        assert_eq!(output.content_type, None);
+
+        // Without a server answering, the wait times out.
+        let server = serve_nothing();
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let result = apps_client
+            .download_request_output_when_ready(
+                &request,
+                Duration::from_millis(10),
+                Duration::from_millis(100),
+            )
+            .await;
+        assert!(matches!(result, Err(SdkError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_download_decodes_content_encoding() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(br#"{"answer":42}"#).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let encoded = |encoding, body| {
+            response(
+                "200 OK",
+                &[
+                    ("Content-Type", "application/json"),
+                    ("Content-Encoding", encoding),
+                ],
+                body,
+            )
+        };
+        let server = serve(vec![
+            encoded("gzip", gzipped),
+            encoded("br", b"compressed".to_vec()),
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let request = models::DownloadRequestOutputRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .build()
+            .unwrap();
+
+        let output = apps_client.download_request_output(&request).await.unwrap();
+        assert_eq!(output.content, Bytes::from_static(br#"{"answer":42}"#));
+        assert_eq!(output.content_length, Some(HeaderValue::from(13)));
+
+        match apps_client.download_request_output(&request).await {
+            Err(SdkError::UnexpectedResponse { detail }) => {
+                assert_eq!(detail, "unsupported content encoding `br`")
+            }
+            other => panic!("expected an unsupported encoding error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_invoke_and_tail_logs() {
+        let log = |id: u8, body: &str| {
+            format!(
+                r#"{{"timestamp":{id},"uuid":"00000000-0000-0000-0000-00000000000{id}","namespace":"default","application":"my-app","resourceAttributes":[],"body":"{body}","logAttributes":""}}"#
+            )
+        };
+        let running =
+            r#"{"id":"request-123","application_version":"1","created_at":1,"function_runs":[]}"#;
+        let finished = r#"{"id":"request-123","outcome":"success","application_version":"1","created_at":1,"function_runs":[]}"#;
+        let first_page = format!(r#"{{"logs":[{}],"nextToken":"t1"}}"#, log(1, "first"));
+        // The server repeats the first log without a new token.
+        let second_page = format!(r#"{{"logs":[{},{}]}}"#, log(1, "first"), log(2, "second"));
+        let server = serve_json(vec![
+            r#"{"request_id":"request-123"}"#,
+            // No logs yet on the first poll.
+            r#"{"logs":[]}"#,
+            running,
+            &first_page,
+            &second_page,
+            r#"{"logs":[]}"#,
+            finished,
+            r#"{"logs":[]}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let request = models::InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body(serde_json::json!({}))
+            .build()
+            .unwrap();
+
+        let (request_id, logs) = apps_client.invoke_and_tail_logs(&request).await.unwrap();
+        assert_eq!(request_id, "request-123");
+        let bodies = logs.map(|log| log.unwrap().body).collect::<Vec<_>>().await;
+        assert_eq!(bodies, vec!["first", "second"]);
+        let logs = "GET /v1/namespaces/default/applications/my-app/logs?requestId=request-123";
+        let status = "GET /v1/namespaces/default/applications/my-app/requests/request-123 HTTP/1.1";
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "POST /v1/namespaces/default/applications/my-app HTTP/1.1".to_string(),
+                format!("{logs} HTTP/1.1"),
+                status.to_string(),
+                format!("{logs} HTTP/1.1"),
+                format!("{logs}&nextToken=t1 HTTP/1.1"),
+                format!("{logs}&nextToken=t1 HTTP/1.1"),
+                status.to_string(),
+                format!("{logs}&nextToken=t1 HTTP/1.1"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_logs_reads_ndjson_and_json() {
+        let log = |id: u8, body: &str| {
+            format!(
+                r#"{{"timestamp":{id},"uuid":"00000000-0000-0000-0000-00000000000{id}","namespace":"default","application":"my-app","resourceAttributes":[],"body":"{body}","logAttributes":""}}"#
+            )
+        };
+        let ndjson = format!("{}\n{}\n", log(1, "first"), log(2, "second"));
+        let json = format!(
+            r#"{{"logs":[{},{}],"nextToken":"t1"}}"#,
+            log(1, "first"),
+            log(2, "second")
+        );
+        let server = serve_bodies(vec![
+            ("application/x-ndjson", &ndjson),
+            ("application/json", &json),
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let request = models::GetLogsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build()
+            .unwrap();
+
+        for _ in 0..2 {
+            let logs = apps_client.stream_logs(&request).await.unwrap();
+            let bodies = logs.map(|log| log.unwrap().body).collect::<Vec<_>>().await;
+            assert_eq!(bodies, vec!["first", "second"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_count_requests() {
+        let server = serve_json(vec![
+            r#"{"requests":[{"id":"r1","created_at":1}],"cursor":"c1","total":1234}"#,
+            r#"{"requests":[{"id":"r1","created_at":1}],"cursor":"c1"}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        assert_eq!(
+            apps_client
+                .count_requests(&"default".into(), &"my-app".into())
+                .await
+                .unwrap(),
+            Some(1234)
+        );
+        assert_eq!(
+            apps_client
+                .count_requests(&"default".into(), &"my-app".into())
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "GET /v1/namespaces/default/applications/my-app/requests?limit=1 HTTP/1.1",
+                "GET /v1/namespaces/default/applications/my-app/requests?limit=1 HTTP/1.1",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_responses() {
+        fn assert_empty_body<T>(result: Result<T, SdkError>, method: &str) {
+            match result {
+                Err(SdkError::UnexpectedResponse { detail }) => {
+                    assert!(
+                        detail.starts_with("empty response body"),
+                        "{method}: {detail}"
+                    )
+                }
+                Err(error) => panic!("{method}: unexpected error: {error:?}"),
+                Ok(_) => panic!("{method}: unexpected success"),
+            }
+        }
+        let client = || {
+            ApplicationsClient::new(
+                ClientBuilder::new(&serve_once("200 OK", "").url)
+                    .build()
+                    .unwrap(),
+            )
+        };
+
+        let list = models::ListApplicationsRequest::builder()
+            .namespace("default".into())
+            .build()
+            .unwrap();
+        assert_empty_body(client().list(&list).await, "list");
+
+        let get = models::GetApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build()
+            .unwrap();
+        assert_empty_body(client().get(&get).await, "get");
+
+        let invoke = models::InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body(serde_json::json!({}))
+            .build()
+            .unwrap();
+        assert_empty_body(client().invoke(&invoke).await, "invoke");
+
+        let list_requests = models::ListRequestsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build()
+            .unwrap();
+        assert_empty_body(
+            client().list_requests(&list_requests).await,
+            "list_requests",
+        );
+
+        let get_request = models::GetRequestRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .build()
+            .unwrap();
+        assert_empty_body(client().get_request(&get_request).await, "get_request");
+
+        let get_function_run = models::GetFunctionRunRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .function_run_id("run-1".into())
+            .build()
+            .unwrap();
+        assert_empty_body(
+            client().get_function_run(&get_function_run).await,
+            "get_function_run",
+        );
+
+        let get_logs = models::GetLogsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build()
+            .unwrap();
+        assert_empty_body(client().get_logs(&get_logs).await, "get_logs");
+    }
+
+    #[tokio::test]
+    async fn test_list_requests_stream() {
+        let server = serve_json(vec![
+            r#"{"requests":[{"id":"request-1","created_at":0},{"id":"request-2","created_at":0}],"cursor":"next"}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::ListRequestsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .limit(2)
+            .build()
+            .unwrap();
+        let ids = apps_client
+            .list_requests_stream(&request)
+            .await
+            .unwrap()
+            .map(|request| request.unwrap().id)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(ids, vec!["request-1", "request-2"]);
+        assert_eq!(
+            server.request_lines(),
+            vec!["GET /v1/namespaces/default/applications/my-app/requests?limit=2 HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_requests_extra_query() {
+        let server = serve_json(vec![r#"{"requests":[]}"#]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::ListRequestsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .limit(10)
+            .extra_query(vec![("status".to_string(), "failed".to_string())])
+            .build()
+            .unwrap();
+        apps_client.list_requests(&request).await.unwrap();
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "GET /v1/namespaces/default/applications/my-app/requests?limit=10&status=failed HTTP/1.1"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_filters_by_tag() {
+        let server = serve_json(vec![
+            r#"{"applications":[
+                {"description":"","entrypoint":{"function_name":"main","input_serializer":"json","output_serializer":"json","output_type_hints_base64":""},"functions":{},"name":"prod-app","tags":{"env":"prod","team":"a"},"version":"1"},
+                {"description":"","entrypoint":{"function_name":"main","input_serializer":"json","output_serializer":"json","output_type_hints_base64":""},"functions":{},"name":"dev-app","tags":{"env":"dev"},"version":"1"}
+            ]}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::ListApplicationsRequest::builder()
+            .namespace("default".into())
+            .tag_filters(vec![("env".to_string(), "prod".to_string())])
+            .build()
+            .unwrap();
+        let applications = apps_client.list(&request).await.unwrap();
+        assert_eq!(
+            applications
+                .applications
+                .iter()
+                .map(|application| application.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["prod-app"]
+        );
+        assert_eq!(
+            server.request_lines(),
+            vec!["GET /v1/namespaces/default/applications HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout() {
+        let server = serve_nothing();
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::GetApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let error = apps_client.get(&request).await.unwrap_err();
+        assert!(matches!(error, SdkError::Timeout));
+        assert_eq!(error.kind(), crate::error::SdkErrorKind::Timeout);
+    }
+
+    #[tokio::test]
+    async fn test_invoke_request_id_with_wrong_type() {
+        let server = serve_json(vec![r#"{"request_id":42}"#]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body(serde_json::json!({}))
+            .build()
+            .unwrap();
+        match apps_client.invoke(&request).await {
+            Err(SdkError::JsonWithError(error)) => {
+                assert_eq!(error.path().to_string(), "request_id")
+            }
+            Err(error) => panic!("expected JsonWithError, got {error:?}"),
+            Ok(_) => panic!("expected JsonWithError, got a successful response"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_progress_until_finished() {
+        let server = serve_json(vec![
+            r#"{"updates":[],"next_token":"t1"}"#,
+            r#"{"updates":[{"RequestStarted":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123"}}],"next_token":null}"#,
+            r#"{"updates":[{"RequestStarted":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123"}},{"RequestFinished":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123"}}],"next_token":"t2"}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let backoff = models::BackoffPolicy::builder()
+            .initial_delay(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let events = apps_client
+            .watch_progress(
+                &"default".into(),
+                &"my-app".into(),
+                &"request-123".into(),
+                backoff,
+            )
+            .collect::<Vec<_>>()
+            .await;
+        // The started event is returned again by the last poll, without being repeated.
+        assert_eq!(events.len(), 2);
+        assert!(!events[0].as_ref().unwrap().is_terminal());
+        assert!(events[1].as_ref().unwrap().is_terminal());
+
+        let request_lines = server.request_lines();
+        let path = "/v1/namespaces/default/applications/my-app/requests/request-123/updates";
+        assert_eq!(
+            request_lines,
+            vec![
+                format!("GET {path} HTTP/1.1"),
+                format!("GET {path}?nextToken=t1 HTTP/1.1"),
+                format!("GET {path}?nextToken=t1 HTTP/1.1"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_timeline() {
+        let server = serve_json(vec![
+            r#"{"updates":[
+                {"RequestStarted":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123","created_at":"2025-01-01T00:00:00Z"}},
+                {"FunctionRunCreated":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123","function_name":"main","function_run_id":"run-1"}}
+            ],"next_token":"t1"}"#,
+            r#"{"updates":[
+                {"FunctionRunCreated":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123","function_name":"other","function_run_id":"run-2"}},
+                {"RequestFinished":{"namespace":"default","application_name":"my-app","application_version":"1","request_id":"request-123","outcome":"success","created_at":"2025-01-01T00:01:00Z"}}
+            ],"next_token":"t2"}"#,
+        ]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let timeline = apps_client
+            .request_timeline(&"default".into(), &"my-app".into(), &"request-123".into())
+            .await
+            .unwrap();
+        assert_eq!(timeline.events.len(), 4);
+        assert_eq!(
+            timeline.started_at().unwrap().to_rfc3339(),
+            "2025-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            timeline.finished_at().unwrap().to_rfc3339(),
+            "2025-01-01T00:01:00+00:00"
+        );
+        assert_eq!(timeline.function_events("main").count(), 1);
+        assert_eq!(
+            timeline.final_outcome(),
+            Some(&models::RequestOutcome::Success)
+        );
+
+        let path = "/v1/namespaces/default/applications/my-app/requests/request-123/updates";
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                format!("GET {path} HTTP/1.1"),
+                format!("GET {path}?nextToken=t1 HTTP/1.1"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_path_segments_are_encoded() {
+        let server = serve(vec![response("404 Not Found", &[], "")]);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::GetApplicationRequest::builder()
+            .namespace("my team".into())
+            .application("a/b?c#d".into())
+            .build()
+            .unwrap();
+        assert!(apps_client.get(&request).await.is_err());
+        assert_eq!(
+            server.request_lines(),
+            vec!["GET /v1/namespaces/my%20team/applications/a%2Fb%3Fc%23d HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invoke_bad_request_is_invalid_request() {
+        let body = r#"{"message":"missing argument: input_text"}"#;
+        let server = serve_once("400 Bad Request", body);
+        let apps_client = ApplicationsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let request = models::InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body(serde_json::json!({}))
+            .build()
+            .unwrap();
+        match apps_client.invoke(&request).await {
+            Err(SdkError::Applications(ApplicationsError::InvalidRequest(message))) => {
+                assert_eq!(message, body)
+            }
+            Err(error) => panic!("expected InvalidRequest, got {error:?}"),
+            Ok(_) => panic!("expected InvalidRequest, got a successful response"),
+        }
+    }
+}
@@ -1,13 +1,26 @@
+use base64::{Engine, prelude::BASE64_STANDARD};
 use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 use futures::Stream;
 use reqwest::header::HeaderValue;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json;
-use std::{collections::HashMap, fmt::Display, pin::Pin};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{Cursor, Write},
+    path::Path,
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
 use uuid::Uuid;
 
-use crate::error::SdkError;
+use crate::{
+    applications::error::ApplicationsError, client::EventStream, error::SdkError,
+    validation::validate_identifier,
+};
 
 /// A custom DateTime<Utc> type that handles RFC3339 timestamps with missing 'Z' timezone indicator.
 /// When deserializing, if the timestamp doesn't end with 'Z', it's automatically appended.
@@ -33,19 +46,26 @@ impl Display for Rfc3339DateTime {
     }
 }
 
+impl FromStr for Rfc3339DateTime {
+    type Err = chrono::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut s = s.to_string();
+        if !s.ends_with("Z") && !s.ends_with("+00:00") {
+            s.push('Z');
+        }
+
+        DateTime::parse_from_rfc3339(&s).map(|dt| Rfc3339DateTime(dt.with_timezone(&Utc)))
+    }
+}
+
 impl<'de> Deserialize<'de> for Rfc3339DateTime {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let mut s = String::deserialize(deserializer)?;
-        if !s.ends_with("Z") && !s.ends_with("+00:00") {
-            s.push('Z');
-        }
-
-        DateTime::parse_from_rfc3339(&s)
-            .map(|dt| Rfc3339DateTime(dt.with_timezone(&Utc)))
-            .map_err(serde::de::Error::custom)
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -75,6 +95,234 @@ impl ApplicationManifest {
     pub fn builder() -> ApplicationManifestBuilder {
         ApplicationManifestBuilder::default()
     }
+
+    /// Load a manifest from a JSON file, and validate it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, isn't a valid manifest, or
+    /// fails [`ApplicationManifest::validate`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::applications::models::ApplicationManifest;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let manifest = ApplicationManifest::from_json_file("application.json")?;
+    /// println!("{} {}", manifest.name, manifest.version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self, SdkError> {
+        let contents = std::fs::read(path)?;
+        let jd = &mut serde_json::Deserializer::from_slice(&contents);
+        let manifest: Self = serde_path_to_error::deserialize(jd)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Load a manifest from a YAML file, and validate it.
+    ///
+    /// Requires the `yaml` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read, isn't a valid manifest, or
+    /// fails [`ApplicationManifest::validate`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::applications::models::ApplicationManifest;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let manifest = ApplicationManifest::from_yaml_file("application.yaml")?;
+    /// println!("{} {}", manifest.name, manifest.version);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Self, SdkError> {
+        let contents = std::fs::read(path)?;
+        let yd = serde_yaml::Deserializer::from_slice(&contents);
+        let manifest: Self = serde_path_to_error::deserialize(yd)?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Write the manifest to a JSON file, in a form [`ApplicationManifest::from_json_file`]
+    /// reads back.
+    pub fn to_json_file(&self, path: impl AsRef<Path>) -> Result<(), SdkError> {
+        let contents = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Check that the manifest has a name and a version, and that its entrypoint
+    /// is one of its functions.
+    pub fn validate(&self) -> Result<(), SdkError> {
+        if self.name.trim().is_empty() {
+            return Err(
+                ApplicationsError::InvalidRequest("manifest name is empty".to_string()).into(),
+            );
+        }
+        if self.version.trim().is_empty() {
+            return Err(
+                ApplicationsError::InvalidRequest("manifest version is empty".to_string()).into(),
+            );
+        }
+        if !self.functions.contains_key(&self.entrypoint.function_name) {
+            return Err(ApplicationsError::InvalidRequest(format!(
+                "entrypoint function `{}` is not one of the manifest functions",
+                self.entrypoint.function_name
+            ))
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Compare the manifest with `other`, for example the deployed manifest of an
+    /// application with the one about to replace it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::applications::models::ApplicationManifest;
+    ///
+    /// # fn example(deployed: &ApplicationManifest) -> Result<(), Box<dyn std::error::Error>> {
+    /// let manifest = ApplicationManifest::from_json_file("application.json")?;
+    /// let diff = deployed.diff(&manifest);
+    /// for name in &diff.added_functions {
+    ///     println!("+ {name}");
+    /// }
+    /// for name in &diff.removed_functions {
+    ///     println!("- {name}");
+    /// }
+    /// for function in &diff.changed_functions {
+    ///     println!("~ {}", function.name);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &ApplicationManifest) -> ManifestDiff {
+        let mut added_functions: Vec<String> = other
+            .functions
+            .keys()
+            .filter(|name| !self.functions.contains_key(*name))
+            .cloned()
+            .collect();
+        added_functions.sort();
+        let mut removed_functions: Vec<String> = self
+            .functions
+            .keys()
+            .filter(|name| !other.functions.contains_key(*name))
+            .cloned()
+            .collect();
+        removed_functions.sort();
+        let mut changed_functions: Vec<FunctionDiff> = self
+            .functions
+            .iter()
+            .filter_map(|(name, function)| {
+                let other_function = other.functions.get(name)?;
+                FunctionDiff::new(name, function, other_function)
+            })
+            .collect();
+        changed_functions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        ManifestDiff {
+            version: Change::between(&self.version, &other.version),
+            entrypoint: Change::between(&self.entrypoint, &other.entrypoint),
+            added_functions,
+            removed_functions,
+            changed_functions,
+        }
+    }
+}
+
+/// Differences between two application manifests, computed by [`ApplicationManifest::diff`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManifestDiff {
+    /// The change of version, if any.
+    pub version: Option<Change<String>>,
+    /// The change of entrypoint, if any.
+    pub entrypoint: Option<Change<Entrypoint>>,
+    /// The functions only in the new manifest, by name.
+    pub added_functions: Vec<String>,
+    /// The functions only in the old manifest, by name.
+    pub removed_functions: Vec<String>,
+    /// The functions in both manifests that differ, by name.
+    pub changed_functions: Vec<FunctionDiff>,
+}
+
+impl ManifestDiff {
+    /// Whether the manifests are the same, apart from their description and tags.
+    pub fn is_empty(&self) -> bool {
+        self.version.is_none()
+            && self.entrypoint.is_none()
+            && self.added_functions.is_empty()
+            && self.removed_functions.is_empty()
+            && self.changed_functions.is_empty()
+    }
+}
+
+/// Differences between two versions of a function of an application manifest.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionDiff {
+    /// The name of the function.
+    pub name: String,
+    /// The change of resources, if any.
+    pub resources: Option<Change<Resources>>,
+    /// The change of timeout, if any.
+    pub timeout_sec: Option<Change<i32>>,
+    /// The change of initialization timeout, if any.
+    pub initialization_timeout_sec: Option<Change<i32>>,
+    /// Whether other fields of the function differ, such as its parameters or retry policy.
+    pub other_changes: bool,
+}
+
+impl FunctionDiff {
+    /// The differences between two versions of a function, or `None` if they're equal.
+    fn new(name: &str, old: &FunctionManifest, new: &FunctionManifest) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+        let others_only = FunctionManifest {
+            resources: new.resources.clone(),
+            timeout_sec: new.timeout_sec,
+            initialization_timeout_sec: new.initialization_timeout_sec,
+            ..old.clone()
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            resources: Change::between(&old.resources, &new.resources),
+            timeout_sec: Change::between(&old.timeout_sec, &new.timeout_sec),
+            initialization_timeout_sec: Change::between(
+                &old.initialization_timeout_sec,
+                &new.initialization_timeout_sec,
+            ),
+            other_changes: others_only != *new,
+        })
+    }
+}
+
+/// A value that differs between two manifests.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change<T> {
+    /// The value in the old manifest.
+    pub from: T,
+    /// The value in the new manifest.
+    pub to: T,
+}
+
+impl<T: Clone + PartialEq> Change<T> {
+    fn between(from: &T, to: &T) -> Option<Self> {
+        (from != to).then(|| Self {
+            from: from.clone(),
+            to: to.clone(),
+        })
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, Builder)]
@@ -93,6 +341,15 @@ impl Entrypoint {
     pub fn builder() -> EntrypointBuilder {
         EntrypointBuilder::default()
     }
+
+    /// Decodes the base64 encoded output type hints into a JSON value.
+    ///
+    /// Returns `None` if the entrypoint doesn't have output type hints.
+    pub fn output_type_hints(&self) -> Result<Option<serde_json::Value>, SdkError> {
+        self.output_type_hints_base64
+            .as_deref()
+            .map_or(Ok(None), decode_output_type_hints)
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, Builder)]
@@ -123,10 +380,44 @@ pub struct FunctionManifest {
     pub max_concurrency: i32,
 }
 
+/// The timeouts of functions created with [`FunctionManifest::builder_with_defaults`].
+pub const DEFAULT_FUNCTION_TIMEOUT_SEC: i32 = 300;
+
 impl FunctionManifest {
     pub fn builder() -> FunctionManifestBuilder {
         FunctionManifestBuilder::default()
     }
+
+    /// A builder with the baseline settings of most functions already set, so
+    /// only the name, the return type and what differs need to be set.
+    ///
+    /// The function gets [`DEFAULT_CPUS`] and [`DEFAULT_MEMORY_MB`], no retries,
+    /// initialization and run timeouts of [`DEFAULT_FUNCTION_TIMEOUT_SEC`], and
+    /// runs one call at a time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tensorlake_cloud_sdk::applications::models::{FunctionManifest, Resources};
+    ///
+    /// let function = FunctionManifest::builder_with_defaults()
+    ///     .name("embed")
+    ///     .return_type(serde_json::json!({"type": "string"}))
+    ///     .resources(Resources::gpu("A100", 1))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(function.timeout_sec, 300);
+    /// ```
+    pub fn builder_with_defaults() -> FunctionManifestBuilder {
+        let mut builder = Self::builder();
+        builder
+            .resources(Resources::cpu(DEFAULT_CPUS, DEFAULT_MEMORY_MB))
+            .retry_policy(RetryPolicy::no_retries())
+            .initialization_timeout_sec(DEFAULT_FUNCTION_TIMEOUT_SEC)
+            .timeout_sec(DEFAULT_FUNCTION_TIMEOUT_SEC)
+            .max_concurrency(1);
+        builder
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, Builder)]
@@ -138,10 +429,53 @@ pub struct Resources {
     pub gpus: Vec<String>,
 }
 
+/// The CPUs given to a function when only its GPUs are specified.
+pub const DEFAULT_CPUS: f64 = 1.0;
+/// The memory given to a function when only its GPUs are specified.
+pub const DEFAULT_MEMORY_MB: i64 = 1024;
+/// The ephemeral disk given to functions created with the `Resources` constructors.
+pub const DEFAULT_EPHEMERAL_DISK_MB: i64 = 2048;
+
 impl Resources {
     pub fn builder() -> ResourcesBuilder {
         ResourcesBuilder::default()
     }
+
+    /// Resources with the given CPUs and memory, the default disk and no GPUs.
+    pub fn cpu(cpus: f64, memory_mb: i64) -> Self {
+        Self {
+            cpus,
+            memory_mb,
+            ephemeral_disk_mb: DEFAULT_EPHEMERAL_DISK_MB,
+            gpus: Vec::new(),
+        }
+    }
+
+    /// Resources with `count` GPUs of the given model, and the default CPUs,
+    /// memory and disk.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tensorlake_cloud_sdk::applications::models::Resources;
+    ///
+    /// let resources = Resources::gpu("A100", 1);
+    /// assert_eq!(resources.gpus, vec!["A100:1".to_string()]);
+    /// ```
+    pub fn gpu(model: &str, count: u32) -> Self {
+        Self {
+            gpus: vec![GpuResources::new(model, count).to_string()],
+            ..Self::cpu(DEFAULT_CPUS, DEFAULT_MEMORY_MB)
+        }
+    }
+}
+
+impl ResourcesBuilder {
+    /// Add a GPU requirement, in addition to any GPUs already set.
+    pub fn gpu(&mut self, gpu: GpuResources) -> &mut Self {
+        self.gpus.get_or_insert_with(Vec::new).push(gpu.to_string());
+        self
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, Builder)]
@@ -156,6 +490,16 @@ impl RetryPolicy {
     pub fn builder() -> RetryPolicyBuilder {
         RetryPolicyBuilder::default()
     }
+
+    /// A policy that never retries failed function runs.
+    pub fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            initial_delay_sec: 1.0,
+            max_delay_sec: 60.0,
+            delay_multiplier: 2.0,
+        }
+    }
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, Builder)]
@@ -228,19 +572,30 @@ impl Parameter {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Allocation {
+    #[serde(alias = "attemptNumber")]
     pub attempt_number: i32,
+    #[serde(alias = "createdAt")]
     pub created_at: u128,
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "executionDurationMs"
+    )]
     pub execution_duration_ms: Option<i64>,
+    #[serde(alias = "executorId")]
     pub executor_id: String,
+    #[serde(alias = "functionExecutorId")]
     pub function_executor_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
     pub id: String,
     pub outcome: FunctionRunOutcome,
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Application {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created_at: Option<i64>,
@@ -258,7 +613,207 @@ pub struct Application {
     pub version: String,
 }
 
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+impl Application {
+    /// Returns true if the application has every one of the given tags.
+    pub fn has_tags(&self, tags: &[(String, String)]) -> bool {
+        tags.iter()
+            .all(|(key, value)| self.tags.get(key) == Some(value))
+    }
+
+    /// Check named arguments against the parameters of the entrypoint function.
+    ///
+    /// Every required parameter without a default value must be present, and
+    /// every argument must match a parameter. Applications whose entrypoint
+    /// doesn't declare its parameters accept any arguments.
+    pub fn validate_invoke_args(
+        &self,
+        args: &HashMap<String, serde_json::Value>,
+    ) -> Result<(), ApplicationsError> {
+        let Some(parameters) = self
+            .functions
+            .get(&self.entrypoint.function_name)
+            .and_then(|function| function.parameters.as_ref())
+        else {
+            return Ok(());
+        };
+
+        let mut missing = parameters
+            .iter()
+            .filter(|p| p.required && p.default_value.is_none() && !args.contains_key(&p.name))
+            .map(|p| p.name.as_str())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            missing.sort();
+            return Err(ApplicationsError::InvalidRequest(format!(
+                "missing required arguments: {}",
+                missing.join(", ")
+            )));
+        }
+
+        let mut unknown = args
+            .keys()
+            .filter(|name| !parameters.iter().any(|p| &p.name == *name))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(ApplicationsError::InvalidRequest(format!(
+                "unknown arguments: {}",
+                unknown.join(", ")
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The functions that can be called through the API, the ones with `is_api` set.
+    pub fn api_functions(&self) -> impl Iterator<Item = (&String, &ApplicationFunction)> {
+        self.functions
+            .iter()
+            .filter(|(_, function)| function.is_api)
+    }
+
+    /// The function the application is invoked through, if it's defined.
+    pub fn entrypoint_function(&self) -> Option<&ApplicationFunction> {
+        self.functions
+            .values()
+            .find(|function| function.name == self.entrypoint.function_name)
+    }
+
+    /// Rebuild the manifest the application was deployed with.
+    ///
+    /// The API doesn't serve the code of deployed applications, so copying an
+    /// application means upserting this manifest with the application's code zip
+    /// from wherever it was built.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApplicationsError::CannotRebuildManifest` if a function has no
+    /// initialization timeout or return type, or a parameter's default value
+    /// isn't valid JSON, since deploying made-up values would change the application.
+    pub fn to_manifest(&self) -> Result<ApplicationManifest, SdkError> {
+        let functions = self
+            .functions
+            .iter()
+            .map(|(key, function)| {
+                let manifest = function.to_manifest()?;
+                Ok((key.clone(), manifest))
+            })
+            .collect::<Result<_, SdkError>>()?;
+        let output_type_hints_base64 = Some(self.entrypoint.output_type_hints_base64.clone())
+            .filter(|hints| !hints.is_empty());
+
+        Ok(ApplicationManifest {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            tags: self.tags.clone(),
+            version: self.version.clone(),
+            functions,
+            entrypoint: Entrypoint {
+                function_name: self.entrypoint.function_name.clone(),
+                input_serializer: self.entrypoint.input_serializer.clone(),
+                output_serializer: self.entrypoint.output_serializer.clone(),
+                output_type_hints_base64,
+            },
+        })
+    }
+}
+
+impl ApplicationFunction {
+    fn to_manifest(&self) -> Result<FunctionManifest, SdkError> {
+        let parameters = self
+            .parameters
+            .iter()
+            .flatten()
+            .map(|parameter| {
+                let mut data_type: DataType = serde_json::from_value(parameter.data_type.clone())?;
+                if let Some(default_value) = &parameter.default_value {
+                    let default_value = serde_json::from_str(default_value).map_err(|error| {
+                        self.cannot_rebuild(format!(
+                            "the default value of parameter `{}` is not valid JSON: {error}",
+                            parameter.name
+                        ))
+                    })?;
+                    data_type.default_value = Some(default_value);
+                }
+                Ok(Parameter {
+                    name: parameter.name.clone(),
+                    description: parameter.description.clone(),
+                    required: parameter.required,
+                    data_type,
+                })
+            })
+            .collect::<Result<_, SdkError>>()?;
+
+        Ok(FunctionManifest {
+            name: self.name.clone(),
+            description: self.description.clone(),
+            is_api: self.is_api,
+            secret_names: self.secret_names.clone(),
+            initialization_timeout_sec: self
+                .initialization_timeout_sec
+                .ok_or_else(|| self.cannot_rebuild("it has no initialization timeout"))?,
+            timeout_sec: self.timeout_sec,
+            resources: Resources {
+                cpus: self.resources.cpus,
+                memory_mb: self.resources.memory_mb,
+                ephemeral_disk_mb: self.resources.ephemeral_disk_mb,
+                gpus: self
+                    .resources
+                    .gpus
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+            },
+            retry_policy: RetryPolicy {
+                max_retries: self.retry_policy.max_retries,
+                initial_delay_sec: self.retry_policy.initial_delay_sec,
+                max_delay_sec: self.retry_policy.max_delay_sec,
+                delay_multiplier: self.retry_policy.delay_multiplier,
+            },
+            cache_key: self.cache_key.clone(),
+            parameters,
+            return_type: self
+                .return_type
+                .clone()
+                .ok_or_else(|| self.cannot_rebuild("it has no return type"))?,
+            placement_constraints: PlacementConstraintsManifest {
+                filter_expressions: self
+                    .placement_constraints
+                    .locations
+                    .clone()
+                    .unwrap_or_default(),
+            },
+            max_concurrency: self.max_concurrency,
+        })
+    }
+
+    fn cannot_rebuild(&self, reason: impl Display) -> SdkError {
+        ApplicationsError::CannotRebuildManifest(format!("function `{}`: {reason}", self.name))
+            .into()
+    }
+}
+
+#[cfg(feature = "semver")]
+impl Application {
+    /// Parses the application version as a semantic version.
+    ///
+    /// Use this to compare versions correctly, since comparing the raw version strings
+    /// orders `1.10.0` before `1.9.0`. Versions that are not semantic versions return an error.
+    pub fn version_semver(&self) -> Result<semver::Version, semver::Error> {
+        semver::Version::parse(&self.version)
+    }
+}
+
+#[cfg(feature = "semver")]
+impl ApplicationManifest {
+    /// Parses the manifest version as a semantic version.
+    pub fn version_semver(&self) -> Result<semver::Version, semver::Error> {
+        semver::Version::parse(&self.version)
+    }
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ApplicationState {
     #[default]
@@ -269,12 +824,16 @@ pub enum ApplicationState {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ApplicationFunction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cache_key: Option<String>,
     pub description: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub initialization_timeout_sec: Option<i32>,
+    /// Whether the function can be called through the API.
+    #[serde(default)]
+    pub is_api: bool,
     pub max_concurrency: i32,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -288,13 +847,18 @@ pub struct ApplicationFunction {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ApplicationRequests {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cursor: Option<String>,
     pub requests: Vec<ShallowRequest>,
+    /// The total number of requests of the application, if the server reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ApplicationsList {
     pub applications: Vec<Application>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -319,11 +883,151 @@ impl std::fmt::Display for CursorDirection {
 #[derive(Clone, Debug, PartialEq)]
 pub struct DownloadOutput {
     pub content_length: Option<HeaderValue>,
+    /// The content type returned by the server, after negotiating the requested `Accept` header.
     pub content_type: Option<HeaderValue>,
     pub content: bytes::Bytes,
 }
 
+impl DownloadOutput {
+    /// Interpret the content according to its content type.
+    ///
+    /// JSON content types (`application/json` and `*+json`) are parsed as JSON,
+    /// `text/*` content types are decoded as UTF-8, and anything else, including
+    /// a missing content type, is returned as binary. Content that doesn't match
+    /// its content type falls back to text, then to binary, so no data is lost.
+    pub fn parse(self) -> OutputValue {
+        let mime = self
+            .content_type
+            .as_ref()
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(|content_type| content_type.split(';').next())
+            .map(|mime| mime.trim().to_ascii_lowercase())
+            .unwrap_or_default();
+        let is_json = mime == "application/json" || mime.ends_with("+json");
+
+        if is_json && let Ok(value) = serde_json::from_slice(&self.content) {
+            return OutputValue::Json(value);
+        }
+        if is_json || mime.starts_with("text/") {
+            return match String::from_utf8(self.content.to_vec()) {
+                Ok(text) => OutputValue::Text(text),
+                Err(_) => OutputValue::Binary(self.content),
+            };
+        }
+        OutputValue::Binary(self.content)
+    }
+}
+
+/// The content of a [`DownloadOutput`], interpreted with [`DownloadOutput::parse`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum OutputValue {
+    Json(serde_json::Value),
+    Text(String),
+    Binary(bytes::Bytes),
+}
+
+/// A stream of output data, downloaded as it arrives.
+///
+/// When the server announces a `Content-Length`, the stream checks that it
+/// received exactly that many bytes, and yields [`SdkError::UnexpectedResponse`]
+/// as its last item otherwise, so a truncated download is never mistaken for a
+/// complete one. Dropping the stream before the end closes the connection.
+pub struct DownloadStream {
+    /// The content type returned by the server.
+    pub content_type: Option<HeaderValue>,
+    /// The number of bytes announced by the server, if any.
+    pub content_length: Option<u64>,
+    inner: Pin<Box<dyn Stream<Item = Result<bytes::Bytes, SdkError>> + Send>>,
+    received: u64,
+    finished: bool,
+}
+
+impl DownloadStream {
+    pub(crate) fn new<S>(
+        content_type: Option<HeaderValue>,
+        content_length: Option<u64>,
+        stream: S,
+    ) -> Self
+    where
+        S: Stream<Item = Result<bytes::Bytes, SdkError>> + Send + 'static,
+    {
+        Self {
+            content_type,
+            content_length,
+            inner: Box::pin(stream),
+            received: 0,
+            finished: false,
+        }
+    }
+
+    /// The number of bytes received so far.
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+}
+
+impl Stream for DownloadStream {
+    type Item = Result<bytes::Bytes, SdkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        let item = match self.inner.as_mut().poll_next(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(item) => item,
+        };
+
+        match item {
+            Some(Ok(chunk)) => {
+                self.received += chunk.len() as u64;
+                match self.content_length {
+                    Some(expected) if self.received > expected => {
+                        self.finished = true;
+                        Poll::Ready(Some(Err(SdkError::UnexpectedResponse {
+                            detail: format!(
+                                "received more than the {expected} bytes announced by Content-Length"
+                            ),
+                        })))
+                    }
+                    _ => Poll::Ready(Some(Ok(chunk))),
+                }
+            }
+            Some(Err(err)) => {
+                self.finished = true;
+                Poll::Ready(Some(Err(err)))
+            }
+            None => {
+                self.finished = true;
+                match self.content_length {
+                    Some(expected) if self.received != expected => {
+                        Poll::Ready(Some(Err(SdkError::UnexpectedResponse {
+                            detail: format!(
+                                "received {} bytes, but Content-Length announced {expected}",
+                                self.received
+                            ),
+                        })))
+                    }
+                    _ => Poll::Ready(None),
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for DownloadStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DownloadStream")
+            .field("content_type", &self.content_type)
+            .field("content_length", &self.content_length)
+            .field("received", &self.received)
+            .finish()
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct EntryPointManifest {
     pub function_name: String,
     pub input_serializer: String,
@@ -331,7 +1035,29 @@ pub struct EntryPointManifest {
     pub output_type_hints_base64: String,
 }
 
+impl EntryPointManifest {
+    /// Decodes the base64 encoded output type hints into a JSON value.
+    ///
+    /// Returns `None` if the application doesn't have output type hints.
+    pub fn output_type_hints(&self) -> Result<Option<serde_json::Value>, SdkError> {
+        decode_output_type_hints(&self.output_type_hints_base64)
+    }
+}
+
+/// Decode base64 encoded output type hints, which are empty when there are none.
+fn decode_output_type_hints(encoded: &str) -> Result<Option<serde_json::Value>, SdkError> {
+    if encoded.is_empty() {
+        return Ok(None);
+    }
+
+    let decoded = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(|e| ApplicationsError::InvalidOutputTypeHints(e.to_string()))?;
+    Ok(Some(serde_json::from_slice(&decoded)?))
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct FunctionResources {
     pub cpus: f64,
     pub gpus: Vec<GpuResources>,
@@ -340,12 +1066,15 @@ pub struct FunctionResources {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct FunctionRun {
+    #[serde(alias = "createdAt")]
     pub created_at: u128,
     pub id: String,
     pub name: String,
     pub namespace: String,
     pub application: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
     pub allocations: Vec<Allocation>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -382,12 +1111,30 @@ pub enum FunctionRunStatus {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct GpuResources {
     pub count: u32,
     pub model: String,
 }
 
+impl GpuResources {
+    pub fn new(model: impl Into<String>, count: u32) -> Self {
+        Self {
+            count,
+            model: model.into(),
+        }
+    }
+}
+
+/// Formats the GPUs as `MODEL:COUNT`, the format of [`Resources::gpus`].
+impl Display for GpuResources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.model, self.count)
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct NodeRetryPolicy {
     pub max_retries: i32,
     pub initial_delay_sec: f64,
@@ -396,6 +1143,7 @@ pub struct NodeRetryPolicy {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ParameterMetadata {
     pub data_type: serde_json::Value,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -407,6 +1155,7 @@ pub struct ParameterMetadata {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct PlacementConstraints {
     /// List of label filter expressions in the format "key=value", "key!=value", etc.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -414,6 +1163,7 @@ pub struct PlacementConstraints {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Request {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -442,13 +1192,91 @@ pub struct Request {
     pub updates_pagination_token: Option<String>,
 }
 
+impl Request {
+    /// Returns true if the request finished, either successfully or with a failure.
+    ///
+    /// A request without an outcome, or with an `Unknown` outcome, is still in progress.
+    pub fn is_complete(&self) -> bool {
+        matches!(
+            self.outcome,
+            Some(RequestOutcome::Success | RequestOutcome::Failure(_))
+        )
+    }
+
+    /// Returns true if the request finished successfully.
+    pub fn is_successful(&self) -> bool {
+        matches!(self.outcome, Some(RequestOutcome::Success))
+    }
+
+    /// Converts a failed request into a [`SdkError::RequestFailed`] with the
+    /// reason of the failure and the error reported by the failing function.
+    ///
+    /// Returns `None` if the request didn't fail.
+    pub fn into_error(self) -> Option<SdkError> {
+        let Some(RequestOutcome::Failure(reason)) = self.outcome else {
+            return None;
+        };
+        let (function_name, message) = match self.request_error {
+            Some(error) => (Some(error.function_name), Some(error.message)),
+            None => (None, None),
+        };
+        Some(SdkError::RequestFailed {
+            request_id: self.id,
+            reason,
+            function_name,
+            message,
+        })
+    }
+
+    /// Returns the function runs in this request that have the given status.
+    pub fn function_runs_by_status(
+        &self,
+        status: FunctionRunStatus,
+    ) -> impl Iterator<Item = &FunctionRun> {
+        self.function_runs
+            .iter()
+            .filter(move |run| run.status == status)
+    }
+
+    /// Returns the function runs in this request that failed, either because their
+    /// status is `Failed` or because they finished with a `Failure` outcome.
+    pub fn failed_runs(&self) -> impl Iterator<Item = &FunctionRun> {
+        self.function_runs.iter().filter(|run| {
+            run.status == FunctionRunStatus::Failed
+                || run.outcome == Some(FunctionRunOutcome::Failure)
+        })
+    }
+
+    /// Returns the first function run of the function with the given name.
+    ///
+    /// A function can run several times in a request; use
+    /// [`Request::function_runs_named`] to get all of its runs.
+    pub fn function_run(&self, function_name: &str) -> Option<&FunctionRun> {
+        self.function_runs
+            .iter()
+            .find(|run| run.name == function_name)
+    }
+
+    /// Returns the function runs of the function with the given name.
+    pub fn function_runs_named<'a>(
+        &'a self,
+        function_name: &'a str,
+    ) -> impl Iterator<Item = &'a FunctionRun> {
+        self.function_runs
+            .iter()
+            .filter(move |run| run.name == function_name)
+    }
+}
+
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct RequestError {
+    #[serde(alias = "functionName")]
     pub function_name: String,
     pub message: String,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum RequestFailureReason {
     #[serde(alias = "unknown")]
     Unknown,
@@ -470,7 +1298,7 @@ pub enum RequestFailureReason {
     OutOfMemory,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RequestOutcome {
     #[default]
@@ -480,13 +1308,16 @@ pub enum RequestOutcome {
 }
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ShallowRequest {
+    #[serde(alias = "createdAt")]
     pub created_at: i64,
     #[serde(rename = "id")]
     pub id: String,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct LogSignal {
     pub timestamp: u64,
     pub uuid: Uuid,
@@ -500,6 +1331,7 @@ pub struct LogSignal {
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct EventsResponse {
     pub logs: Vec<LogSignal>,
@@ -507,6 +1339,21 @@ pub struct EventsResponse {
     pub next_token: Option<String>,
 }
 
+impl EventsResponse {
+    /// Format each log as `[timestamp] body`.
+    pub fn plain_lines(&self) -> Vec<String> {
+        self.format_lines(|log| format!("[{}] {}", log.timestamp, log.body))
+    }
+
+    /// Format each log with a custom formatter.
+    pub fn format_lines<F>(&self, formatter: F) -> Vec<String>
+    where
+        F: Fn(&LogSignal) -> String,
+    {
+        self.logs.iter().map(formatter).collect()
+    }
+}
+
 pub trait RequestEventMetadata {
     fn namespace(&self) -> &str;
     fn application_name(&self) -> &str;
@@ -516,7 +1363,10 @@ pub trait RequestEventMetadata {
     fn set_created_at(&mut self, date: DateTime<Utc>);
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// A change in the state of a request, as sent by the progress updates endpoints.
+///
+/// Events are externally tagged on the wire: `{"RequestStarted": {...}}`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum RequestStateChangeEvent {
     RequestStarted(RequestStartedEvent),
     FunctionRunCreated(FunctionRunCreated),
@@ -655,9 +1505,78 @@ impl RequestStateChangeEvent {
             RequestStateChangeEvent::FunctionRunAssigned(_) => "Allocation Created",
         }
     }
+
+    /// The name of the function the event is about, if it's about a function.
+    pub fn function_name(&self) -> Option<&str> {
+        match self {
+            RequestStateChangeEvent::FunctionRunCreated(event) => Some(&event.function_name),
+            RequestStateChangeEvent::FunctionRunCompleted(event) => Some(&event.function_name),
+            RequestStateChangeEvent::FunctionRunMatchedCache(event) => Some(&event.function_name),
+            RequestStateChangeEvent::AllocationCreated(event) => Some(&event.function_name),
+            RequestStateChangeEvent::AllocationCompleted(event) => Some(&event.function_name),
+            RequestStateChangeEvent::FunctionRunAssigned(event) => Some(&event.function_name),
+            RequestStateChangeEvent::RequestStarted(_)
+            | RequestStateChangeEvent::RequestProgressUpdated(_)
+            | RequestStateChangeEvent::RequestFinished(_) => None,
+        }
+    }
+}
+
+/// Every progress event of a request, in the order the server reported them.
+///
+/// Returned by [`ApplicationsClient::request_timeline`](super::ApplicationsClient::request_timeline)
+/// to inspect a request after it finished.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestTimeline {
+    pub events: Vec<RequestStateChangeEvent>,
+}
+
+impl RequestTimeline {
+    /// When the request started, if the `RequestStarted` event has a timestamp.
+    pub fn started_at(&self) -> Option<&DateTime<Utc>> {
+        self.events
+            .iter()
+            .find(|event| matches!(event, RequestStateChangeEvent::RequestStarted(_)))
+            .and_then(RequestStateChangeEvent::created_at)
+    }
+
+    /// When the request finished, if the `RequestFinished` event has a timestamp.
+    pub fn finished_at(&self) -> Option<&DateTime<Utc>> {
+        self.finished_event()
+            .and_then(|event| event.created_at.as_ref())
+            .map(|created_at| &created_at.0)
+    }
+
+    /// The events about the function with the given name.
+    pub fn function_events<'a>(
+        &'a self,
+        function_name: &'a str,
+    ) -> impl Iterator<Item = &'a RequestStateChangeEvent> {
+        self.events
+            .iter()
+            .filter(move |event| event.function_name() == Some(function_name))
+    }
+
+    /// The outcome of the request, or `None` if the timeline doesn't include
+    /// the `RequestFinished` event.
+    pub fn final_outcome(&self) -> Option<&RequestOutcome> {
+        self.finished_event().map(|event| &event.outcome)
+    }
+
+    /// Whether the timeline includes the `RequestFinished` event.
+    pub fn is_finished(&self) -> bool {
+        self.finished_event().is_some()
+    }
+
+    fn finished_event(&self) -> Option<&RequestFinishedEvent> {
+        self.events.iter().rev().find_map(|event| match event {
+            RequestStateChangeEvent::RequestFinished(event) => Some(event),
+            _ => None,
+        })
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum StringKind {
     String(String),
@@ -679,7 +1598,7 @@ impl Default for StringKind {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum FloatKind {
     Float(f64),
@@ -697,21 +1616,27 @@ impl FloatKind {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 #[non_exhaustive]
 pub struct RequestProgressUpdated {
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub namespace: String,
-    #[serde(default, skip_serializing_if = "String::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "String::is_empty",
+        alias = "applicationName"
+    )]
     pub application_name: String,
-    #[serde(default)]
+    #[serde(default, alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "functionName")]
     pub function_name: String,
-    #[serde(default)]
+    #[serde(default, alias = "functionRunId")]
     pub function_run_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "allocationId")]
     pub allocation_id: String,
     #[serde(default)]
     pub message: StringKind,
@@ -721,10 +1646,24 @@ pub struct RequestProgressUpdated {
     pub total: Option<FloatKind>,
     #[serde(default)]
     pub attributes: Option<serde_json::Value>,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
+impl RequestProgressUpdated {
+    /// Returns the attributes of the update, if they're a JSON object.
+    pub fn as_map(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.attributes.as_ref()?.as_object()
+    }
+
+    /// Returns the attribute `key` deserialized as `T`.
+    ///
+    /// Returns `None` if the attribute is missing or doesn't deserialize as `T`.
+    pub fn attribute<T: serde::de::DeserializeOwned>(&self, key: &str) -> Option<T> {
+        T::deserialize(self.as_map()?.get(key)?).ok()
+    }
+}
+
 impl RequestEventMetadata for RequestProgressUpdated {
     fn namespace(&self) -> &str {
         &self.namespace
@@ -751,15 +1690,130 @@ impl RequestEventMetadata for RequestProgressUpdated {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct RequestFinishedEvent {
-    pub namespace: String,
-    pub application_name: String,
-    pub application_version: String,
-    pub request_id: String,
+/// Latest progress reported by a function.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FunctionProgress {
+    pub step: Option<f64>,
+    pub total: Option<f64>,
+    pub message: Option<String>,
+}
+
+impl FunctionProgress {
+    /// Returns the completed fraction, between 0.0 and 1.0, if both step and total are known.
+    pub fn fraction(&self) -> Option<f64> {
+        match (self.step, self.total) {
+            (Some(step), Some(total)) if total > 0.0 => Some((step / total).clamp(0.0, 1.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Aggregates `RequestProgressUpdated` events to track the progress of a request.
+///
+/// ```rust
+/// use tensorlake_cloud_sdk::applications::models::{ProgressTracker, RequestStateChangeEvent};
+///
+/// fn render(events: &[RequestStateChangeEvent]) {
+///     let mut tracker = ProgressTracker::new();
+///     for event in events {
+///         tracker.apply(event);
+///     }
+///     if let Some(fraction) = tracker.fraction() {
+///         println!("{:.0}%", fraction * 100.0);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ProgressTracker {
+    functions: HashMap<String, FunctionProgress>,
+    finished: bool,
+    succeeded: bool,
+}
+
+impl ProgressTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the tracker with a request event. Events other than
+    /// `RequestProgressUpdated` and `RequestFinished` are ignored.
+    pub fn apply(&mut self, event: &RequestStateChangeEvent) {
+        match event {
+            RequestStateChangeEvent::RequestProgressUpdated(update) => {
+                let progress = self
+                    .functions
+                    .entry(update.function_name.clone())
+                    .or_default();
+                if let Some(step) = update.step.as_ref().and_then(FloatKind::as_f64) {
+                    progress.step = Some(step);
+                }
+                if let Some(total) = update.total.as_ref().and_then(FloatKind::as_f64) {
+                    progress.total = Some(total);
+                }
+                if let Some(message) = update.message.as_str().filter(|m| !m.is_empty()) {
+                    progress.message = Some(message.to_string());
+                }
+            }
+            RequestStateChangeEvent::RequestFinished(finished) => {
+                self.finished = true;
+                self.succeeded = finished.outcome == RequestOutcome::Success;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the latest progress reported by each function, keyed by function name.
+    pub fn functions(&self) -> &HashMap<String, FunctionProgress> {
+        &self.functions
+    }
+
+    /// Returns the latest progress reported by a function.
+    pub fn function(&self, function_name: &str) -> Option<&FunctionProgress> {
+        self.functions.get(function_name)
+    }
+
+    /// Returns true once a `RequestFinished` event has been applied.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the overall completed fraction, between 0.0 and 1.0.
+    ///
+    /// The overall fraction is the average of the functions that reported both a step
+    /// and a total. It's 1.0 once the request finished successfully, and `None` if
+    /// no function reported measurable progress yet. A failed request keeps the
+    /// progress reached before it failed.
+    pub fn fraction(&self) -> Option<f64> {
+        if self.succeeded {
+            return Some(1.0);
+        }
+
+        let fractions: Vec<f64> = self
+            .functions
+            .values()
+            .filter_map(FunctionProgress::fraction)
+            .collect();
+        if fractions.is_empty() {
+            return None;
+        }
+
+        Some(fractions.iter().sum::<f64>() / fractions.len() as f64)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
+pub struct RequestFinishedEvent {
+    pub namespace: String,
+    #[serde(alias = "applicationName")]
+    pub application_name: String,
+    #[serde(alias = "applicationVersion")]
+    pub application_version: String,
+    #[serde(alias = "requestId")]
+    pub request_id: String,
     #[serde(default)]
     pub outcome: RequestOutcome,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -789,13 +1843,17 @@ impl RequestEventMetadata for RequestFinishedEvent {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct RequestStartedEvent {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -825,15 +1883,21 @@ impl RequestEventMetadata for RequestStartedEvent {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct FunctionRunCreated {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
+    #[serde(alias = "functionRunId")]
     pub function_run_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -864,17 +1928,25 @@ impl RequestEventMetadata for FunctionRunCreated {
 }
 
 /// Event emitted when an allocation (execution attempt) is created and assigned to an executor
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct AllocationCreated {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
+    #[serde(alias = "functionRunId")]
     pub function_run_id: String,
+    #[serde(alias = "allocationId")]
     pub allocation_id: String,
+    #[serde(alias = "executorId")]
     pub executor_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -907,7 +1979,7 @@ impl RequestEventMetadata for AllocationCreated {
 /// @deprecated Use AllocationCreated instead
 pub type FunctionRunAssigned = AllocationCreated;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum FunctionRunOutcomeSummary {
     Unknown,
@@ -920,20 +1992,30 @@ pub enum FunctionRunOutcomeSummary {
 /// Note: In older server versions (before allocation/function-run lifecycle split),
 /// this event included `allocation_id`. For backward compatibility, `allocation_id`
 /// is kept as an optional field. New server versions will not include it.
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct FunctionRunCompleted {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
+    #[serde(alias = "functionRunId")]
     pub function_run_id: String,
     /// Optional for backward compatibility with older servers.
     /// New servers (with allocation lifecycle) won't include this field.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        alias = "allocationId"
+    )]
     pub allocation_id: Option<String>,
     pub outcome: FunctionRunOutcomeSummary,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -964,17 +2046,24 @@ impl RequestEventMetadata for FunctionRunCompleted {
 }
 
 /// Event emitted when an allocation (execution attempt) completes with an outcome
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct AllocationCompleted {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
+    #[serde(alias = "functionRunId")]
     pub function_run_id: String,
+    #[serde(alias = "allocationId")]
     pub allocation_id: String,
     pub outcome: FunctionRunOutcomeSummary,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -1004,15 +2093,21 @@ impl RequestEventMetadata for AllocationCompleted {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct FunctionRunMatchedCache {
     pub namespace: String,
+    #[serde(alias = "applicationName")]
     pub application_name: String,
+    #[serde(alias = "applicationVersion")]
     pub application_version: String,
+    #[serde(alias = "requestId")]
     pub request_id: String,
+    #[serde(alias = "functionName")]
     pub function_name: String,
+    #[serde(alias = "functionRunId")]
     pub function_run_id: String,
-    #[serde(default)]
+    #[serde(default, alias = "createdAt")]
     pub created_at: Option<Rfc3339DateTime>,
 }
 
@@ -1042,14 +2137,126 @@ impl RequestEventMetadata for FunctionRunMatchedCache {
     }
 }
 
+/// Define a string identifier type, so that identifiers of different kinds
+/// can't be passed in place of one another.
+macro_rules! identifier {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// The identifier as a string slice.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Convert the identifier into its string.
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl std::ops::Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(value: &String) -> Self {
+                Self(value.clone())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(value: &str) -> Self {
+                Self(value.to_string())
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+identifier!(
+    /// The name of a namespace.
+    Namespace
+);
+
+identifier!(
+    /// The name of an application.
+    ApplicationName
+);
+
+identifier!(
+    /// The ID of a request, as returned when an application is invoked.
+    ///
+    /// Identifiers of other kinds aren't accepted in its place:
+    ///
+    /// ```rust,compile_fail
+    /// use tensorlake_cloud_sdk::applications::models::{ApplicationName, GetRequestRequest};
+    ///
+    /// let application = ApplicationName::from("my-app");
+    /// GetRequestRequest::builder().request_id(application);
+    /// ```
+    RequestId
+);
+
+identifier!(
+    /// The ID of a function call of a request.
+    FunctionCallId
+);
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct CheckFunctionOutputRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl CheckFunctionOutputRequest {
@@ -1058,12 +2265,26 @@ impl CheckFunctionOutputRequest {
     }
 }
 
+impl CheckFunctionOutputRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DeleteApplicationRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl DeleteApplicationRequest {
@@ -1072,12 +2293,19 @@ impl DeleteApplicationRequest {
     }
 }
 
+impl DeleteApplicationRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DeleteFunctionRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
     #[builder(setter(into))]
     pub function_name: String,
 }
@@ -1088,14 +2316,26 @@ impl DeleteFunctionRequest {
     }
 }
 
+impl DeleteFunctionRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DeleteRequestRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl DeleteRequestRequest {
@@ -1104,16 +2344,31 @@ impl DeleteRequestRequest {
     }
 }
 
+impl DeleteRequestRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DownloadFunctionOutputRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
-    #[builder(setter(into))]
-    pub function_call_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    pub function_call_id: FunctionCallId,
+    /// Media type to request from the server through the `Accept` header.
+    #[builder(default, setter(into, strip_option))]
+    pub accept: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl DownloadFunctionOutputRequest {
@@ -1122,14 +2377,30 @@ impl DownloadFunctionOutputRequest {
     }
 }
 
+impl DownloadFunctionOutputRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DownloadRequestOutputRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    /// Media type to request from the server through the `Accept` header.
+    #[builder(default, setter(into, strip_option))]
+    pub accept: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl DownloadRequestOutputRequest {
@@ -1138,12 +2409,26 @@ impl DownloadRequestOutputRequest {
     }
 }
 
+impl DownloadRequestOutputRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct GetApplicationRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl GetApplicationRequest {
@@ -1152,16 +2437,28 @@ impl GetApplicationRequest {
     }
 }
 
+impl GetApplicationRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct GetRequestRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
     #[builder(setter(into, strip_option), default)]
     pub updates_pagination_token: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl GetRequestRequest {
@@ -1170,13 +2467,69 @@ impl GetRequestRequest {
     }
 }
 
+impl GetRequestRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
+#[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct GetFunctionRunRequest {
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    pub function_run_id: FunctionCallId,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
+}
+
+impl GetFunctionRunRequest {
+    pub fn builder() -> GetFunctionRunRequestBuilder {
+        GetFunctionRunRequestBuilder::default()
+    }
+}
+
+impl GetFunctionRunRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        validate_identifier("function_run_id", self.function_run_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct InvokeApplicationRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Option<Result<serde_json::Value, String>>",
+            build = "match &self.body {
+                Some(Ok(body)) => body.clone(),
+                Some(Err(error)) => return Err(error.clone().into()),
+                None => return Err(derive_builder::UninitializedFieldError::new(\"body\").into()),
+            }"
+        )
+    )]
     pub body: serde_json::Value,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl InvokeApplicationRequest {
@@ -1185,24 +2538,107 @@ impl InvokeApplicationRequest {
     }
 }
 
+impl InvokeApplicationRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        Ok(())
+    }
+}
+
+impl InvokeApplicationRequestBuilder {
+    /// Set the input to invoke the application with.
+    pub fn body<VALUE: Into<serde_json::Value>>(&mut self, body: VALUE) -> &mut Self {
+        self.body = Some(Ok(body.into()));
+        self
+    }
+
+    /// Set the input to invoke the application with from any serializable value.
+    ///
+    /// Serialization errors are returned by [`InvokeApplicationRequestBuilder::build`].
+    pub fn body_json<T: Serialize>(&mut self, body: &T) -> &mut Self {
+        self.body =
+            Some(serde_json::to_value(body).map_err(|error| format!("invalid body: {error}")));
+        self
+    }
+}
+
+/// Body of the response to a successful invocation.
+#[derive(Debug, Deserialize)]
+pub(crate) struct InvokeAck {
+    pub(crate) request_id: RequestId,
+}
+
 /// Response from invoking an application
 pub enum InvokeResponse {
     /// The request ID of the invocation
-    RequestId(String),
+    RequestId(RequestId),
     /// A stream of progress events
-    Stream(Pin<Box<dyn Stream<Item = Result<RequestStateChangeEvent, SdkError>> + Send>>),
+    Stream(ProgressUpdatesStream),
+}
+
+impl InvokeResponse {
+    /// Returns the request ID of the invocation.
+    ///
+    /// Returns an error if the response is an `InvokeResponse::Stream`.
+    pub fn into_request_id(self) -> Result<RequestId, SdkError> {
+        match self {
+            InvokeResponse::RequestId(id) => Ok(id),
+            InvokeResponse::Stream(_) => Err(SdkError::UnexpectedResponse {
+                detail: "expected InvokeResponse::RequestId, got InvokeResponse::Stream"
+                    .to_string(),
+            }),
+        }
+    }
+
+    /// Returns the stream of progress events of the invocation.
+    ///
+    /// Returns an error if the response is an `InvokeResponse::RequestId`.
+    pub fn into_stream(self) -> Result<ProgressUpdatesStream, SdkError> {
+        match self {
+            InvokeResponse::Stream(stream) => Ok(stream),
+            InvokeResponse::RequestId(_) => Err(SdkError::UnexpectedResponse {
+                detail: "expected InvokeResponse::Stream, got InvokeResponse::RequestId"
+                    .to_string(),
+            }),
+        }
+    }
+}
+
+fn validate_list_limit(limit: Option<Option<i32>>) -> Result<(), String> {
+    match limit.flatten() {
+        Some(limit) if limit <= 0 => Err(format!("limit must be positive, got {limit}")),
+        _ => Ok(()),
+    }
 }
 
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct ListApplicationsRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
+    pub namespace: Namespace,
+    /// The maximum number of applications to return. The server may return fewer.
     #[builder(default, setter(strip_option))]
     pub limit: Option<i32>,
     #[builder(default, setter(into, strip_option))]
     pub cursor: Option<String>,
     #[builder(default, setter(strip_option))]
     pub direction: Option<CursorDirection>,
+    /// Only list the applications that have all of these tags, as key and value.
+    ///
+    /// The server doesn't filter by tag, so the filters are applied to each page
+    /// of the response, and pages can hold fewer applications than `limit`.
+    #[builder(default, setter(into))]
+    pub tag_filters: Vec<(String, String)>,
+    /// Additional query parameters, appended after the typed ones, for
+    /// server-side filters that don't have a typed field yet.
+    #[builder(default, setter(into))]
+    pub extra_query: Vec<(String, String)>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl ListApplicationsRequest {
@@ -1211,18 +2647,35 @@ impl ListApplicationsRequest {
     }
 }
 
+impl ListApplicationsRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_list_limit(self.limit)
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct ListRequestsRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    /// The maximum number of requests to return. The server may return fewer.
     #[builder(default, setter(strip_option))]
     pub limit: Option<i32>,
     #[builder(default, setter(into, strip_option))]
     pub cursor: Option<String>,
     #[builder(default, setter(strip_option))]
     pub direction: Option<CursorDirection>,
+    /// Additional query parameters, appended after the typed ones, for
+    /// server-side filters that don't have a typed field yet.
+    #[builder(default, setter(into))]
+    pub extra_query: Vec<(String, String)>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl ListRequestsRequest {
@@ -1231,14 +2684,24 @@ impl ListRequestsRequest {
     }
 }
 
+impl ListRequestsRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_list_limit(self.limit)
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct StreamProgressRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    /// Start the stream right after the update identified by this token, as returned
+    /// by a paginated [`ProgressUpdatesRequest`], instead of at the current update.
+    #[builder(default, setter(into, strip_option))]
+    pub from_token: Option<String>,
 }
 
 impl StreamProgressRequest {
@@ -1247,13 +2710,32 @@ impl StreamProgressRequest {
     }
 }
 
+impl StreamProgressRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct UpsertApplicationRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
+    pub namespace: Namespace,
     pub application_manifest: ApplicationManifest,
     #[builder(setter(into))]
     pub code_zip: Vec<u8>,
+    /// The version the application is expected to have on the server.
+    /// The upsert fails with `SdkError::PreconditionFailed` if it doesn't match.
+    #[builder(default, setter(into, strip_option))]
+    pub expected_version: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl UpsertApplicationRequest {
@@ -1262,16 +2744,200 @@ impl UpsertApplicationRequest {
     }
 }
 
+impl UpsertApplicationRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        Ok(())
+    }
+}
+
+/// Request of `ApplicationsClient::upsert_from_reader`, which streams the code
+/// zip from a reader instead of holding it in the request.
 #[derive(Builder, Debug)]
-pub struct GetLogsRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(default, setter(into, strip_option))]
-    pub request_id: Option<String>,
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct UpsertApplicationFromReaderRequest {
+    pub namespace: Namespace,
+    pub application_manifest: ApplicationManifest,
+    /// The version the application is expected to have on the server.
+    /// The upsert fails with `SdkError::PreconditionFailed` if it doesn't match.
     #[builder(default, setter(into, strip_option))]
-    pub container_id: Option<String>,
+    pub expected_version: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
+}
+
+impl UpsertApplicationFromReaderRequest {
+    pub fn builder() -> UpsertApplicationFromReaderRequestBuilder {
+        UpsertApplicationFromReaderRequestBuilder::default()
+    }
+}
+
+impl UpsertApplicationFromReaderRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        Ok(())
+    }
+}
+
+/// Name of the manifest entry inside an application code zip.
+pub const CODE_MANIFEST_FILE_NAME: &str = ".tensorlake_code_manifest.json";
+
+/// Manifest describing where each function lives inside an application code zip.
+///
+/// It's stored in the zip as [`CODE_MANIFEST_FILE_NAME`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeManifest {
+    /// Functions in the code zip, keyed by function name.
+    pub functions: HashMap<String, CodeFunctionEntry>,
+}
+
+impl CodeManifest {
+    /// Create an empty code manifest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a function defined in the given Python module, like `app` for `app.py`.
+    pub fn function(
+        mut self,
+        name: impl Into<String>,
+        module_import_name: impl Into<String>,
+    ) -> Self {
+        let name = name.into();
+        self.functions.insert(
+            name.clone(),
+            CodeFunctionEntry {
+                name,
+                module_import_name: module_import_name.into(),
+            },
+        );
+        self
+    }
+
+    /// Check that every entry has a name matching its key and a module to import it from.
+    pub fn validate(&self) -> Result<(), ApplicationsError> {
+        for (key, entry) in &self.functions {
+            if entry.name != *key {
+                return Err(ApplicationsError::InvalidRequest(format!(
+                    "code manifest entry `{key}` has mismatched function name `{}`",
+                    entry.name
+                )));
+            }
+            if entry.module_import_name.is_empty() {
+                return Err(ApplicationsError::InvalidRequest(format!(
+                    "code manifest entry `{key}` has an empty module import name"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Serialize the manifest into the JSON stored in the code zip.
+    pub fn to_json(&self) -> Result<String, ApplicationsError> {
+        self.validate()?;
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
+/// Location of a single function inside an application code zip.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CodeFunctionEntry {
+    /// The name of the function.
+    pub name: String,
+    /// The Python module the function is imported from.
+    pub module_import_name: String,
+}
+
+/// Create the code zip for an application from the files in a directory.
+///
+/// Every file under `path` is added to the zip, relative to `path`, along with
+/// the code manifest entry. A manifest file already present in the directory
+/// is replaced by `manifest`.
+///
+/// # Errors
+///
+/// Returns an error if the manifest is invalid or the directory can't be read.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tensorlake_cloud_sdk::applications::models::{CodeManifest, code_zip_from_dir};
+///
+/// fn example() -> Result<(), Box<dyn std::error::Error>> {
+///     let manifest = CodeManifest::new().function("my_function", "app");
+///     let code_zip = code_zip_from_dir("./my-app", &manifest)?;
+///     Ok(())
+/// }
+/// ```
+pub fn code_zip_from_dir(
+    path: impl AsRef<Path>,
+    manifest: &CodeManifest,
+) -> Result<Vec<u8>, SdkError> {
+    fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                collect_files(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    manifest.validate()?;
+
+    let root = path.as_ref();
+    let mut files = Vec::new();
+    collect_files(root, &mut files)?;
+    files.sort();
+
+    let mut zip_data = Vec::new();
+    let mut zip_writer = zip::ZipWriter::new(Cursor::new(&mut zip_data));
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let zip_error = |error: zip::result::ZipError| ApplicationsError::CodeZip(error.to_string());
+
+    zip_writer
+        .start_file(CODE_MANIFEST_FILE_NAME, options)
+        .map_err(zip_error)?;
+    zip_writer.write_all(manifest.to_json()?.as_bytes())?;
+
+    for file in files {
+        let name = file
+            .strip_prefix(root)
+            .unwrap_or(&file)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if name == CODE_MANIFEST_FILE_NAME {
+            continue;
+        }
+
+        zip_writer.start_file(name, options).map_err(zip_error)?;
+        zip_writer.write_all(&std::fs::read(&file)?)?;
+    }
+
+    zip_writer.finish().map_err(zip_error)?;
+    drop(zip_writer);
+
+    Ok(zip_data)
+}
+
+#[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct GetLogsRequest {
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    #[builder(default, setter(strip_option))]
+    pub request_id: Option<RequestId>,
+    #[builder(default, setter(into, strip_option))]
+    pub container_id: Option<String>,
     #[builder(default, setter(into, strip_option))]
     pub function: Option<String>,
     #[builder(default, setter(into, strip_option))]
@@ -1284,6 +2950,12 @@ pub struct GetLogsRequest {
     pub ignore: Option<String>,
     #[builder(default, setter(into, strip_option))]
     pub function_executor: Option<String>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl GetLogsRequest {
@@ -1292,14 +2964,26 @@ impl GetLogsRequest {
     }
 }
 
+impl GetLogsRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier(
+            "request_id",
+            self.request_id.as_ref().and_then(Option::as_ref),
+        )?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Clone, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct ProgressUpdatesRequest {
-    #[builder(setter(into))]
-    pub namespace: String,
-    #[builder(setter(into))]
-    pub application: String,
-    #[builder(setter(into))]
-    pub request_id: String,
+    pub namespace: Namespace,
+    pub application: ApplicationName,
+    pub request_id: RequestId,
+    /// How the updates are fetched. Defaults to pages from the first update.
+    #[builder(default)]
     pub mode: ProgressUpdatesRequestMode,
 }
 
@@ -1309,14 +2993,97 @@ pub enum ProgressUpdatesRequestMode {
     Stream,
 }
 
+impl Default for ProgressUpdatesRequestMode {
+    fn default() -> Self {
+        ProgressUpdatesRequestMode::Paginated(None)
+    }
+}
+
 impl ProgressUpdatesRequest {
     pub fn builder() -> ProgressUpdatesRequestBuilder {
         ProgressUpdatesRequestBuilder::default()
     }
 }
 
-type ProgressUpdatesStream =
-    Pin<Box<dyn Stream<Item = Result<RequestStateChangeEvent, SdkError>> + Send>>;
+impl ProgressUpdatesRequestBuilder {
+    /// Fetch the updates page by page, from the first update.
+    pub fn paginated(&mut self) -> &mut Self {
+        self.mode(ProgressUpdatesRequestMode::Paginated(None))
+    }
+
+    /// Fetch the updates page by page, from the page identified by `token`,
+    /// the `next_token` of a previous page.
+    pub fn paginated_from(&mut self, token: impl Into<String>) -> &mut Self {
+        self.mode(ProgressUpdatesRequestMode::Paginated(Some(token.into())))
+    }
+
+    /// Stream the updates as server-sent events.
+    pub fn stream(&mut self) -> &mut Self {
+        self.mode(ProgressUpdatesRequestMode::Stream)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("namespace", self.namespace.as_ref())?;
+        validate_identifier("application", self.application.as_ref())?;
+        validate_identifier("request_id", self.request_id.as_ref())?;
+        Ok(())
+    }
+}
+
+/// A stream of request progress events.
+pub type ProgressUpdatesStream = EventStream<RequestStateChangeEvent>;
+
+/// Delays between polls that returned no new progress updates.
+///
+/// The first empty poll waits `initial_delay`, and every following one waits
+/// `multiplier` times longer, up to `max_delay`. New updates reset the delay.
+#[derive(Builder, Clone, Debug, PartialEq)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct BackoffPolicy {
+    #[builder(default = "Duration::from_millis(250)")]
+    pub initial_delay: Duration,
+    #[builder(default = "Duration::from_secs(10)")]
+    pub max_delay: Duration,
+    #[builder(default = "2.0")]
+    pub multiplier: f64,
+}
+
+impl BackoffPolicy {
+    pub fn builder() -> BackoffPolicyBuilder {
+        BackoffPolicyBuilder::default()
+    }
+
+    /// The delay to wait after `current`, or the initial delay if there is none.
+    ///
+    /// A delay that can't be multiplied, because it would overflow or the
+    /// multiplier isn't valid, is capped at `max_delay`.
+    pub fn next_delay(&self, current: Option<Duration>) -> Duration {
+        match current {
+            None => self.initial_delay.min(self.max_delay),
+            Some(current) => Duration::try_from_secs_f64(current.as_secs_f64() * self.multiplier)
+                .map_or(self.max_delay, |delay| delay.min(self.max_delay)),
+        }
+    }
+}
+
+impl BackoffPolicyBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.multiplier {
+            Some(multiplier) if !multiplier.is_finite() || multiplier < 1.0 => Err(format!(
+                "multiplier must be a finite number of at least 1, got {multiplier}"
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        BackoffPolicy::builder()
+            .build()
+            .expect("the default backoff policy is valid")
+    }
+}
 
 pub enum ProgressUpdatesResponse {
     /// A JSON object containing progress updates
@@ -1354,6 +3121,7 @@ impl ProgressUpdatesResponse {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ProgressUpdatesJson {
     pub updates: Vec<RequestStateChangeEvent>,
     pub next_token: Option<String>,
@@ -1365,6 +3133,44 @@ mod tests {
     use chrono::Datelike;
     use serde_json::json;
 
+    #[test]
+    fn test_download_output_parse() {
+        let output = |content_type: Option<&'static str>, content: &'static [u8]| DownloadOutput {
+            content_length: None,
+            content_type: content_type.map(HeaderValue::from_static),
+            content: bytes::Bytes::from_static(content),
+        };
+
+        assert_eq!(
+            output(Some("application/json; charset=utf-8"), br#"{"a":1}"#).parse(),
+            OutputValue::Json(json!({"a": 1}))
+        );
+        assert_eq!(
+            output(Some("application/problem+json"), b"[1]").parse(),
+            OutputValue::Json(json!([1]))
+        );
+        assert_eq!(
+            output(Some("application/json"), b"not json").parse(),
+            OutputValue::Text("not json".to_string())
+        );
+        assert_eq!(
+            output(Some("text/plain"), b"hello").parse(),
+            OutputValue::Text("hello".to_string())
+        );
+        assert_eq!(
+            output(Some("text/plain"), &[0xff, 0xfe]).parse(),
+            OutputValue::Binary(bytes::Bytes::from_static(&[0xff, 0xfe]))
+        );
+        assert_eq!(
+            output(Some("application/octet-stream"), b"hello").parse(),
+            OutputValue::Binary(bytes::Bytes::from_static(b"hello"))
+        );
+        assert_eq!(
+            output(None, b"{}").parse(),
+            OutputValue::Binary(bytes::Bytes::from_static(b"{}"))
+        );
+    }
+
     #[test]
     fn test_rfc3339_datetime_with_z() {
         let json = json!("2024-01-15T10:30:45Z");
@@ -1411,7 +3217,7 @@ mod tests {
         // Test that serializing Rfc3339DateTime produces a plain string, not a nested struct
         let now = chrono::Utc::now();
         let rfc_dt = Rfc3339DateTime(now);
-        let serialized = serde_json::to_value(&rfc_dt).unwrap();
+        let serialized = serde_json::to_value(rfc_dt).unwrap();
 
         // Should be a string, not an object
         assert!(
@@ -1626,4 +3432,1127 @@ mod tests {
             _ => panic!("Expected AllocationCompleted variant"),
         }
     }
+
+    fn function_run(id: &str, status: FunctionRunStatus) -> FunctionRun {
+        FunctionRun {
+            created_at: 0,
+            id: id.to_string(),
+            name: "my-func".to_string(),
+            namespace: "test-ns".to_string(),
+            application: "test-app".to_string(),
+            application_version: "1.0".to_string(),
+            allocations: Vec::new(),
+            outcome: None,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_request_function_runs_by_status() {
+        let mut completed_with_failure = function_run("run-3", FunctionRunStatus::Completed);
+        completed_with_failure.outcome = Some(FunctionRunOutcome::Failure);
+
+        let request = Request {
+            id: "req-123".to_string(),
+            outcome: None,
+            failure_reason: None,
+            application_version: "1.0".to_string(),
+            created_at: 0,
+            request_error: None,
+            function_runs: vec![
+                function_run("run-1", FunctionRunStatus::Running),
+                function_run("run-2", FunctionRunStatus::Failed),
+                completed_with_failure,
+            ],
+            progress_updates: Vec::new(),
+            updates_pagination_token: None,
+        };
+
+        let running: Vec<_> = request
+            .function_runs_by_status(FunctionRunStatus::Running)
+            .map(|run| run.id.as_str())
+            .collect();
+        assert_eq!(running, vec!["run-1"]);
+
+        let failed: Vec<_> = request.failed_runs().map(|run| run.id.as_str()).collect();
+        assert_eq!(failed, vec!["run-2", "run-3"]);
+    }
+
+    #[test]
+    fn test_request_accepts_both_casings() {
+        let snake_case = json!({
+            "id": "req-123",
+            "application_version": "1.0",
+            "created_at": 1,
+            "request_error": {"function_name": "my-func", "message": "boom"},
+            "function_runs": [{
+                "created_at": 2,
+                "id": "run-1",
+                "name": "my-func",
+                "namespace": "test-ns",
+                "application": "test-app",
+                "application_version": "1.0",
+                "allocations": [{
+                    "attempt_number": 1,
+                    "created_at": 3,
+                    "execution_duration_ms": 40,
+                    "executor_id": "executor-1",
+                    "function_executor_id": "fe-1",
+                    "function_name": "my-func",
+                    "id": "alloc-1",
+                    "outcome": "failure"
+                }],
+                "status": "failed"
+            }],
+            "progress_updates": [{"RequestFinished": {
+                "namespace": "test-ns",
+                "application_name": "test-app",
+                "application_version": "1.0",
+                "request_id": "req-123",
+                "outcome": "success"
+            }}],
+            "updates_pagination_token": "token"
+        });
+        let camel_case = json!({
+            "id": "req-123",
+            "applicationVersion": "1.0",
+            "createdAt": 1,
+            "requestError": {"functionName": "my-func", "message": "boom"},
+            "functionRuns": [{
+                "createdAt": 2,
+                "id": "run-1",
+                "name": "my-func",
+                "namespace": "test-ns",
+                "application": "test-app",
+                "applicationVersion": "1.0",
+                "allocations": [{
+                    "attemptNumber": 1,
+                    "createdAt": 3,
+                    "executionDurationMs": 40,
+                    "executorId": "executor-1",
+                    "functionExecutorId": "fe-1",
+                    "functionName": "my-func",
+                    "id": "alloc-1",
+                    "outcome": "failure"
+                }],
+                "status": "failed"
+            }],
+            "progressUpdates": [{"RequestFinished": {
+                "namespace": "test-ns",
+                "applicationName": "test-app",
+                "applicationVersion": "1.0",
+                "requestId": "req-123",
+                "outcome": "success"
+            }}],
+            "updatesPaginationToken": "token"
+        });
+
+        let from_snake_case: Request = serde_json::from_value(snake_case).unwrap();
+        let from_camel_case: Request = serde_json::from_value(camel_case).unwrap();
+        assert_eq!(
+            serde_json::to_value(&from_snake_case).unwrap(),
+            serde_json::to_value(&from_camel_case).unwrap()
+        );
+        let allocation = &from_camel_case.function_runs[0].allocations[0];
+        assert_eq!(allocation.execution_duration_ms, Some(40));
+        assert_eq!(allocation.function_executor_id, "fe-1");
+        assert_eq!(
+            from_camel_case.request_error.unwrap().function_name,
+            "my-func"
+        );
+    }
+
+    #[test]
+    fn test_progress_updates_request_modes() {
+        let mut builder = ProgressUpdatesRequest::builder();
+        builder
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into());
+        assert!(matches!(
+            builder.build().unwrap().mode,
+            ProgressUpdatesRequestMode::Paginated(None)
+        ));
+        assert!(matches!(
+            builder.paginated_from("token").build().unwrap().mode,
+            ProgressUpdatesRequestMode::Paginated(Some(ref token)) if token == "token"
+        ));
+        assert!(matches!(
+            builder.paginated().build().unwrap().mode,
+            ProgressUpdatesRequestMode::Paginated(None)
+        ));
+        assert!(matches!(
+            builder.stream().build().unwrap().mode,
+            ProgressUpdatesRequestMode::Stream
+        ));
+    }
+
+    #[test]
+    fn test_request_progress_updated_attributes() {
+        let update: RequestProgressUpdated = serde_json::from_value(json!({
+            "request_id": "req-123",
+            "attributes": {"stage": "embedding", "docs_done": 42}
+        }))
+        .unwrap();
+        assert_eq!(update.as_map().unwrap().len(), 2);
+        assert_eq!(
+            update.attribute::<String>("stage").as_deref(),
+            Some("embedding")
+        );
+        assert_eq!(update.attribute::<u64>("docs_done"), Some(42));
+        assert_eq!(update.attribute::<u64>("stage"), None);
+        assert_eq!(update.attribute::<u64>("missing"), None);
+
+        let update: RequestProgressUpdated = serde_json::from_value(json!({
+            "request_id": "req-123",
+            "attributes": ["not", "a", "map"]
+        }))
+        .unwrap();
+        assert!(update.as_map().is_none());
+        assert_eq!(update.attribute::<String>("stage"), None);
+    }
+
+    #[test]
+    fn test_request_into_error() {
+        let mut request: Request = serde_json::from_value(json!({
+            "id": "req-123",
+            "application_version": "1.0",
+            "created_at": 0,
+            "request_error": {"function_name": "my-func", "message": "boom"},
+            "function_runs": []
+        }))
+        .unwrap();
+        request.outcome = Some(RequestOutcome::Success);
+        assert!(request.clone().into_error().is_none());
+
+        request.outcome = Some(RequestOutcome::Failure(RequestFailureReason::FunctionError));
+        let error = request.clone().into_error().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Request req-123 failed: FunctionError in `my-func`: boom"
+        );
+        assert!(matches!(
+            error,
+            SdkError::RequestFailed {
+                reason: RequestFailureReason::FunctionError,
+                ..
+            }
+        ));
+
+        request.request_error = None;
+        assert_eq!(
+            request.into_error().unwrap().to_string(),
+            "Request req-123 failed: FunctionError"
+        );
+    }
+
+    #[test]
+    fn test_request_function_runs_named() {
+        let mut helper = function_run("run-2", FunctionRunStatus::Running);
+        helper.name = "helper".to_string();
+
+        let request = Request {
+            id: "req-123".to_string(),
+            outcome: None,
+            failure_reason: None,
+            application_version: "1.0".to_string(),
+            created_at: 0,
+            request_error: None,
+            function_runs: vec![
+                function_run("run-1", FunctionRunStatus::Completed),
+                helper,
+                function_run("run-3", FunctionRunStatus::Running),
+            ],
+            progress_updates: Vec::new(),
+            updates_pagination_token: None,
+        };
+
+        assert_eq!(request.function_run("helper").unwrap().id, "run-2");
+        assert_eq!(request.function_run("my-func").unwrap().id, "run-1");
+        assert!(request.function_run("missing").is_none());
+
+        let runs: Vec<_> = request
+            .function_runs_named("my-func")
+            .map(|run| run.id.as_str())
+            .collect();
+        assert_eq!(runs, vec!["run-1", "run-3"]);
+    }
+
+    #[test]
+    fn test_request_completion() {
+        let mut request: Request = serde_json::from_value(json!({
+            "id": "req-123",
+            "application_version": "1.0",
+            "created_at": 0,
+            "function_runs": []
+        }))
+        .unwrap();
+        assert!(!request.is_complete());
+        assert!(!request.is_successful());
+
+        request.outcome = Some(RequestOutcome::Unknown);
+        assert!(!request.is_complete());
+        assert!(!request.is_successful());
+
+        request.outcome = Some(RequestOutcome::Failure(RequestFailureReason::FunctionError));
+        assert!(request.is_complete());
+        assert!(!request.is_successful());
+
+        request.outcome = Some(RequestOutcome::Success);
+        assert!(request.is_complete());
+        assert!(request.is_successful());
+    }
+
+    #[test]
+    fn test_entrypoint_output_type_hints() {
+        let data_type = DataType::builder().typ("string").build().unwrap();
+        let entrypoint = EntryPointManifest {
+            function_name: "main".to_string(),
+            input_serializer: "json".to_string(),
+            output_serializer: "json".to_string(),
+            output_type_hints_base64: BASE64_STANDARD.encode(data_type.to_json_string().unwrap()),
+        };
+        assert_eq!(
+            entrypoint.output_type_hints().unwrap(),
+            Some(data_type.to_json_value().unwrap())
+        );
+
+        // A `null` schema is a schema, not missing hints.
+        let entrypoint = EntryPointManifest {
+            output_type_hints_base64: BASE64_STANDARD.encode("null"),
+            ..EntryPointManifest::default()
+        };
+        assert_eq!(
+            entrypoint.output_type_hints().unwrap(),
+            Some(serde_json::Value::Null)
+        );
+
+        let entrypoint = EntryPointManifest::default();
+        assert_eq!(entrypoint.output_type_hints().unwrap(), None);
+    }
+
+    #[test]
+    fn test_progress_tracker() {
+        let progress = |function_name: &str, step: serde_json::Value, total: f64| {
+            serde_json::from_value::<RequestStateChangeEvent>(json!({
+                "RequestProgressUpdated": {
+                    "request_id": "req-123",
+                    "function_name": function_name,
+                    "message": "working",
+                    "step": step,
+                    "total": total
+                }
+            }))
+            .unwrap()
+        };
+
+        let mut tracker = ProgressTracker::new();
+        assert_eq!(tracker.fraction(), None);
+
+        tracker.apply(&progress("extract", json!(1.0), 4.0));
+        tracker.apply(&progress("extract", json!("2"), 4.0));
+        tracker.apply(&progress("summarize", json!(1.0), 1.0));
+
+        let extract = tracker.function("extract").unwrap();
+        assert_eq!(extract.fraction(), Some(0.5));
+        assert_eq!(extract.message.as_deref(), Some("working"));
+        assert_eq!(tracker.fraction(), Some(0.75));
+        assert!(!tracker.is_finished());
+
+        tracker.apply(
+            &serde_json::from_value(json!({
+                "RequestFinished": {
+                    "namespace": "test-ns",
+                    "application_name": "test-app",
+                    "application_version": "1.0",
+                    "request_id": "req-123",
+                    "outcome": "success"
+                }
+            }))
+            .unwrap(),
+        );
+        assert!(tracker.is_finished());
+        assert_eq!(tracker.fraction(), Some(1.0));
+
+        let mut failed = ProgressTracker::new();
+        failed.apply(&progress("extract", json!(1.0), 4.0));
+        failed.apply(
+            &serde_json::from_value(json!({
+                "RequestFinished": {
+                    "namespace": "test-ns",
+                    "application_name": "test-app",
+                    "application_version": "1.0",
+                    "request_id": "req-123",
+                    "outcome": {"failure": "functionerror"}
+                }
+            }))
+            .unwrap(),
+        );
+        assert!(failed.is_finished());
+        assert_eq!(failed.fraction(), Some(0.25));
+    }
+
+    #[test]
+    fn test_invoke_response_into_request_id() {
+        let response = InvokeResponse::RequestId(RequestId::from("req-123"));
+        assert_eq!(response.into_request_id().unwrap(), "req-123");
+
+        let response = InvokeResponse::RequestId(RequestId::from("req-123"));
+        assert!(matches!(
+            response.into_stream(),
+            Err(SdkError::UnexpectedResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_code_zip_from_dir() {
+        use std::io::Read;
+
+        let root = std::env::temp_dir().join(format!("code-zip-test-{}", rand::random::<u32>()));
+        std::fs::create_dir_all(root.join("pkg")).unwrap();
+        std::fs::write(root.join("app.py"), "print('app')").unwrap();
+        std::fs::write(root.join("pkg/utils.py"), "print('utils')").unwrap();
+        std::fs::write(root.join(CODE_MANIFEST_FILE_NAME), "stale").unwrap();
+
+        let manifest = CodeManifest::new().function("my_function", "app");
+        let result = code_zip_from_dir(&root, &manifest);
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(result.unwrap())).unwrap();
+        let mut names = archive.file_names().map(String::from).collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![CODE_MANIFEST_FILE_NAME, "app.py", "pkg/utils.py"]
+        );
+
+        let mut contents = String::new();
+        archive
+            .by_name(CODE_MANIFEST_FILE_NAME)
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        let decoded: CodeManifest = serde_json::from_str(&contents).unwrap();
+        assert_eq!(decoded, manifest);
+    }
+
+    #[test]
+    fn test_code_manifest_json() {
+        let manifest = CodeManifest::new()
+            .function("simple_test_func", "app")
+            .function("helper_func", "app");
+
+        let value: serde_json::Value = serde_json::from_str(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "functions": {
+                    "simple_test_func": {
+                        "name": "simple_test_func",
+                        "module_import_name": "app"
+                    },
+                    "helper_func": {
+                        "name": "helper_func",
+                        "module_import_name": "app"
+                    }
+                }
+            })
+        );
+
+        let mut invalid = manifest.clone();
+        invalid.functions.get_mut("helper_func").unwrap().name = "other".to_string();
+        assert!(invalid.validate().is_err());
+        assert!(CodeManifest::new().function("f", "").to_json().is_err());
+    }
+
+    #[test]
+    fn test_events_response_plain_lines() {
+        let log = |timestamp: u64, body: &str| LogSignal {
+            timestamp,
+            uuid: Uuid::nil(),
+            namespace: "default".to_string(),
+            application: "my-app".to_string(),
+            resource_attributes: Vec::new(),
+            body: body.to_string(),
+            log_attributes: String::new(),
+        };
+        let response = EventsResponse {
+            logs: vec![log(1, "starting"), log(2, "done")],
+            next_token: None,
+        };
+
+        assert_eq!(response.plain_lines(), vec!["[1] starting", "[2] done"]);
+        assert_eq!(
+            response.format_lines(|log| log.body.to_uppercase()),
+            vec!["STARTING", "DONE"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_fields() {
+        let result = serde_json::from_value::<GpuResources>(json!({
+            "count": 1,
+            "model": "A100",
+            "vendor": "nvidia",
+        }));
+        assert_eq!(result.is_err(), cfg!(feature = "strict-deserialization"));
+    }
+
+    #[test]
+    fn test_backoff_policy() {
+        let backoff = BackoffPolicy::builder()
+            .initial_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        let first = backoff.next_delay(None);
+        assert_eq!(first, Duration::from_secs(1));
+        let second = backoff.next_delay(Some(first));
+        assert_eq!(second, Duration::from_secs(2));
+        assert_eq!(
+            backoff.next_delay(Some(Duration::from_secs(4))),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            BackoffPolicy::builder().build().unwrap(),
+            BackoffPolicy::default()
+        );
+
+        for multiplier in [0.5, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(
+                BackoffPolicy::builder()
+                    .multiplier(multiplier)
+                    .build()
+                    .is_err(),
+                "{multiplier}"
+            );
+        }
+        let overflowing = BackoffPolicy {
+            multiplier: f64::MAX,
+            ..backoff
+        };
+        assert_eq!(
+            overflowing.next_delay(Some(Duration::from_secs(1))),
+            Duration::from_secs(5)
+        );
+    }
+
+    #[test]
+    fn test_resources_constructors() {
+        let resources = Resources::cpu(2.0, 4096);
+        assert_eq!(resources.cpus, 2.0);
+        assert_eq!(resources.memory_mb, 4096);
+        assert_eq!(resources.ephemeral_disk_mb, DEFAULT_EPHEMERAL_DISK_MB);
+        assert!(resources.gpus.is_empty());
+
+        let resources = Resources::gpu("H100", 2);
+        assert_eq!(resources.cpus, DEFAULT_CPUS);
+        assert_eq!(resources.gpus, vec!["H100:2".to_string()]);
+
+        let resources = Resources::builder()
+            .cpus(4.0)
+            .memory_mb(8192)
+            .ephemeral_disk_mb(10240)
+            .gpu(GpuResources::new("A100", 1))
+            .gpu(GpuResources::new("H100", 1))
+            .build()
+            .unwrap();
+        assert_eq!(resources.gpus, vec!["A100:1", "H100:1"]);
+    }
+
+    #[test]
+    fn test_blank_identifiers_are_rejected() {
+        let error = GetApplicationRequest::builder()
+            .namespace("".into())
+            .application("my-app".into())
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("namespace"));
+
+        let error = GetRequestRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("   ".into())
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("request_id"));
+
+        let error = GetFunctionRunRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .function_run_id("".into())
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("function_run_id"));
+
+        assert!(
+            GetLogsRequest::builder()
+                .namespace("default".into())
+                .application(" ".into())
+                .build()
+                .is_err()
+        );
+        assert!(
+            GetApplicationRequest::builder()
+                .namespace("default".into())
+                .application("my-app".into())
+                .build()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_list_limit_validation() {
+        let build = |limit| {
+            ListApplicationsRequest::builder()
+                .namespace("default".into())
+                .limit(limit)
+                .build()
+        };
+        assert!(build(0).is_err());
+        assert!(build(-1).is_err());
+        assert_eq!(build(100).unwrap().limit, Some(100));
+
+        let request = ListRequestsRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build()
+            .unwrap();
+        assert_eq!(request.limit, None);
+        assert!(
+            ListRequestsRequest::builder()
+                .namespace("default".into())
+                .application("my-app".into())
+                .limit(0)
+                .build()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_stream_checks_content_length() {
+        use futures::StreamExt;
+
+        let chunks = || {
+            futures::stream::iter(vec![
+                Ok(bytes::Bytes::from_static(b"hello ")),
+                Ok(bytes::Bytes::from_static(b"world")),
+            ])
+        };
+
+        let complete = DownloadStream::new(None, Some(11), chunks())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(complete.len(), 2);
+        assert!(complete.iter().all(Result::is_ok));
+
+        let mut truncated = DownloadStream::new(None, Some(20), chunks());
+        assert!(truncated.next().await.unwrap().is_ok());
+        assert!(truncated.next().await.unwrap().is_ok());
+        assert!(matches!(
+            truncated.next().await,
+            Some(Err(SdkError::UnexpectedResponse { .. }))
+        ));
+        assert!(truncated.next().await.is_none());
+
+        let unknown_length = DownloadStream::new(None, None, chunks())
+            .collect::<Vec<_>>()
+            .await;
+        assert!(unknown_length.iter().all(Result::is_ok));
+    }
+
+    #[test]
+    fn test_invoke_application_request_body_json() {
+        #[derive(Serialize)]
+        struct Input {
+            text: String,
+        }
+
+        let request = InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body_json(&Input {
+                text: "hello".to_string(),
+            })
+            .build()
+            .unwrap();
+        assert_eq!(request.body, json!({"text": "hello"}));
+
+        let invalid = InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body_json(&HashMap::from([((1, 2), "not a string key")]))
+            .build();
+        assert!(invalid.is_err());
+
+        let into = InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .body("hello")
+            .build()
+            .unwrap();
+        assert_eq!(into.body, json!("hello"));
+
+        let missing = InvokeApplicationRequest::builder()
+            .namespace("default".into())
+            .application("my-app".into())
+            .build();
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_request_state_change_event_wire_format() {
+        let metadata = json!({
+            "namespace": "test-ns",
+            "application_name": "test-app",
+            "application_version": "1.0",
+            "request_id": "req-123",
+            "created_at": "2024-01-15T10:30:45Z"
+        });
+        let with = |fields: serde_json::Value| {
+            let mut event = metadata.clone();
+            event
+                .as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            event
+        };
+
+        let events = [
+            ("RequestStarted", with(json!({}))),
+            (
+                "FunctionRunCreated",
+                with(json!({"function_name": "my-func", "function_run_id": "run-456"})),
+            ),
+            (
+                "FunctionRunCompleted",
+                with(json!({
+                    "function_name": "my-func",
+                    "function_run_id": "run-456",
+                    "outcome": "success"
+                })),
+            ),
+            (
+                "FunctionRunMatchedCache",
+                with(json!({"function_name": "my-func", "function_run_id": "run-456"})),
+            ),
+            (
+                "AllocationCreated",
+                with(json!({
+                    "function_name": "my-func",
+                    "function_run_id": "run-456",
+                    "allocation_id": "alloc-789",
+                    "executor_id": "exec-001"
+                })),
+            ),
+            (
+                "AllocationCompleted",
+                with(json!({
+                    "function_name": "my-func",
+                    "function_run_id": "run-456",
+                    "allocation_id": "alloc-789",
+                    "outcome": "failure"
+                })),
+            ),
+            (
+                "RequestProgressUpdated",
+                with(json!({
+                    "function_name": "my-func",
+                    "function_run_id": "run-456",
+                    "allocation_id": "alloc-789",
+                    "message": "halfway there",
+                    "step": 5.0,
+                    "total": 10.0,
+                    "attributes": {"stage": "embedding"}
+                })),
+            ),
+            (
+                "RequestFinished",
+                with(json!({"outcome": {"failure": "FunctionError"}})),
+            ),
+        ];
+
+        for (tag, fields) in events {
+            let wire = json!({ tag: fields });
+            let event: RequestStateChangeEvent = serde_json::from_value(wire.clone())
+                .unwrap_or_else(|error| panic!("failed to deserialize {tag}: {error}"));
+            assert_eq!(event.as_str(), tag);
+            assert_eq!(event.request_id(), "req-123");
+            assert!(event.created_at().is_some(), "{tag} lost created_at");
+
+            // Serializing keeps the externally tagged shape sent by the server.
+            let serialized = serde_json::to_value(&event).unwrap();
+            assert!(serialized.get(tag).is_some(), "{tag} changed its tag");
+            let round_trip: RequestStateChangeEvent = serde_json::from_value(serialized).unwrap();
+            assert_eq!(round_trip, event, "{tag} didn't round trip");
+        }
+
+        let finished: RequestStateChangeEvent = serde_json::from_value(json!({
+            "RequestFinished": with(json!({"outcome": {"failure": "FunctionError"}}))
+        }))
+        .unwrap();
+        let RequestStateChangeEvent::RequestFinished(finished) = finished else {
+            panic!("expected RequestFinished");
+        };
+        assert_eq!(
+            finished.outcome,
+            RequestOutcome::Failure(RequestFailureReason::FunctionError)
+        );
+
+        // Internally tagged payloads are not part of the server's wire format.
+        assert!(
+            serde_json::from_value::<RequestStateChangeEvent>(with(
+                json!({"type": "RequestStarted"})
+            ))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_invoke_args() {
+        let parameter =
+            |name: &str, required: bool, default_value: Option<&str>| ParameterMetadata {
+                name: name.to_string(),
+                required,
+                default_value: default_value.map(String::from),
+                ..Default::default()
+            };
+        let mut app = Application {
+            entrypoint: EntryPointManifest {
+                function_name: "main".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        app.functions.insert(
+            "main".to_string(),
+            ApplicationFunction {
+                parameters: Some(vec![
+                    parameter("text", true, None),
+                    parameter("language", true, Some("en")),
+                    parameter("verbose", false, None),
+                ]),
+                ..Default::default()
+            },
+        );
+
+        let args = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| (name.to_string(), json!(true)))
+                .collect::<HashMap<_, _>>()
+        };
+
+        assert!(app.validate_invoke_args(&args(&["text"])).is_ok());
+        assert!(
+            app.validate_invoke_args(&args(&["text", "language", "verbose"]))
+                .is_ok()
+        );
+        let missing = app.validate_invoke_args(&args(&["verbose"])).unwrap_err();
+        assert!(
+            missing
+                .to_string()
+                .contains("missing required arguments: text")
+        );
+        let unknown = app
+            .validate_invoke_args(&args(&["text", "colour"]))
+            .unwrap_err();
+        assert!(unknown.to_string().contains("unknown arguments: colour"));
+
+        app.functions.get_mut("main").unwrap().parameters = None;
+        assert!(app.validate_invoke_args(&args(&["anything"])).is_ok());
+    }
+
+    #[test]
+    fn test_application_to_manifest() {
+        let mut app = Application {
+            name: "my-app".to_string(),
+            version: "1.0.0".to_string(),
+            entrypoint: EntryPointManifest {
+                function_name: "main".to_string(),
+                input_serializer: "json".to_string(),
+                output_serializer: "json".to_string(),
+                output_type_hints_base64: String::new(),
+            },
+            ..Default::default()
+        };
+        app.functions.insert(
+            "main".to_string(),
+            ApplicationFunction {
+                name: "main".to_string(),
+                parameters: Some(vec![ParameterMetadata {
+                    name: "text".to_string(),
+                    data_type: json!({"type": "string"}),
+                    default_value: Some("\"hello\"".to_string()),
+                    ..Default::default()
+                }]),
+                resources: FunctionResources {
+                    cpus: 2.0,
+                    gpus: vec![GpuResources::new("A100", 1)],
+                    memory_mb: 4096,
+                    ephemeral_disk_mb: 2048,
+                },
+                initialization_timeout_sec: Some(300),
+                return_type: Some(json!({"type": "string"})),
+                ..Default::default()
+            },
+        );
+        app.functions.insert(
+            "helper".to_string(),
+            ApplicationFunction {
+                name: "helper".to_string(),
+                is_api: true,
+                initialization_timeout_sec: Some(300),
+                return_type: Some(json!(null)),
+                ..Default::default()
+            },
+        );
+
+        let manifest = app.to_manifest().unwrap();
+        assert_eq!(manifest.name, "my-app");
+        assert_eq!(manifest.entrypoint.function_name, "main");
+        assert_eq!(manifest.entrypoint.output_type_hints_base64, None);
+
+        // The stored flag is kept, even for the entrypoint.
+        let main = &manifest.functions["main"];
+        assert!(!main.is_api);
+        assert_eq!(main.resources.gpus, vec!["A100:1"]);
+        assert_eq!(main.parameters[0].data_type.typ.as_deref(), Some("string"));
+        assert_eq!(
+            main.parameters[0].data_type.default_value,
+            Some(json!("hello"))
+        );
+        assert!(manifest.functions["helper"].is_api);
+
+        let cannot_rebuild = |app: &Application| {
+            matches!(
+                app.to_manifest(),
+                Err(SdkError::Applications(
+                    ApplicationsError::CannotRebuildManifest(_)
+                ))
+            )
+        };
+        let mut missing_timeout = app.clone();
+        missing_timeout
+            .functions
+            .get_mut("helper")
+            .unwrap()
+            .initialization_timeout_sec = None;
+        assert!(cannot_rebuild(&missing_timeout));
+        let mut missing_return_type = app.clone();
+        missing_return_type
+            .functions
+            .get_mut("helper")
+            .unwrap()
+            .return_type = None;
+        assert!(cannot_rebuild(&missing_return_type));
+        let mut invalid_default = app.clone();
+        invalid_default
+            .functions
+            .get_mut("main")
+            .unwrap()
+            .parameters
+            .as_mut()
+            .unwrap()[0]
+            .default_value = Some("hello".to_string());
+        assert!(cannot_rebuild(&invalid_default));
+    }
+
+    #[cfg(feature = "strict-deserialization")]
+    #[test]
+    fn test_strict_deserialization_rejects_unknown_response_fields() {
+        assert!(serde_json::from_str::<ShallowRequest>(r#"{"id":"r1","created_at":1}"#).is_ok());
+        assert!(
+            serde_json::from_str::<ShallowRequest>(r#"{"id":"r1","created_at":1,"new":true}"#)
+                .is_err()
+        );
+
+        // Manifests are written by users, so their extra keys are still ignored.
+        let mut manifest = serde_json::to_value(ApplicationManifest::default()).unwrap();
+        manifest["comment"] = json!("deployed by CI");
+        assert!(serde_json::from_value::<ApplicationManifest>(manifest).is_ok());
+    }
+
+    /// A valid manifest with a `main` API function as its entrypoint.
+    fn file_manifest() -> ApplicationManifest {
+        let mut manifest = ApplicationManifest {
+            name: "my-app".to_string(),
+            version: "1.0.0".to_string(),
+            entrypoint: Entrypoint {
+                function_name: "main".to_string(),
+                input_serializer: "json".to_string(),
+                output_serializer: "json".to_string(),
+                output_type_hints_base64: None,
+            },
+            ..Default::default()
+        };
+        manifest.functions.insert(
+            "main".to_string(),
+            FunctionManifest {
+                name: "main".to_string(),
+                is_api: true,
+                ..Default::default()
+            },
+        );
+        manifest
+    }
+
+    #[test]
+    fn test_manifest_json_file_round_trip() {
+        let mut manifest = file_manifest();
+
+        let path = std::env::temp_dir().join(format!("manifest-{}.json", rand::random::<u32>()));
+        manifest.to_json_file(&path).unwrap();
+        let loaded = ApplicationManifest::from_json_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), manifest);
+
+        manifest.entrypoint.function_name = "missing".to_string();
+        assert!(matches!(
+            manifest.validate(),
+            Err(SdkError::Applications(ApplicationsError::InvalidRequest(_)))
+        ));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_manifest_yaml_file_round_trip() {
+        let mut manifest = file_manifest();
+
+        let path = std::env::temp_dir().join(format!("manifest-{}.yaml", rand::random::<u32>()));
+        std::fs::write(&path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+        let loaded = ApplicationManifest::from_yaml_file(&path);
+        assert_eq!(loaded.unwrap(), manifest);
+
+        // Invalid manifests are rejected when loaded.
+        manifest.entrypoint.function_name = "missing".to_string();
+        std::fs::write(&path, serde_yaml::to_string(&manifest).unwrap()).unwrap();
+        let loaded = ApplicationManifest::from_yaml_file(&path);
+        std::fs::write(&path, "name: [").unwrap();
+        let malformed = ApplicationManifest::from_yaml_file(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            loaded,
+            Err(SdkError::Applications(ApplicationsError::InvalidRequest(_)))
+        ));
+        assert!(matches!(malformed, Err(SdkError::Yaml(_))));
+    }
+
+    #[test]
+    fn test_manifest_diff() {
+        let function = |name: &str, timeout_sec: i32| FunctionManifest {
+            name: name.to_string(),
+            timeout_sec,
+            resources: Resources::cpu(1.0, 1024),
+            ..Default::default()
+        };
+        let old = ApplicationManifest {
+            name: "my-app".to_string(),
+            version: "1".to_string(),
+            functions: HashMap::from([
+                ("main".to_string(), function("main", 60)),
+                ("helper".to_string(), function("helper", 60)),
+                ("legacy".to_string(), function("legacy", 60)),
+            ]),
+            ..Default::default()
+        };
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = old.clone();
+        new.version = "2".to_string();
+        new.functions.remove("legacy");
+        new.functions
+            .insert("extra".to_string(), function("extra", 60));
+        new.functions.get_mut("main").unwrap().timeout_sec = 120;
+        let helper = new.functions.get_mut("helper").unwrap();
+        helper.resources = Resources::gpu("A100", 1);
+        helper.description = "Helps".to_string();
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.version,
+            Some(Change {
+                from: "1".to_string(),
+                to: "2".to_string()
+            })
+        );
+        assert_eq!(diff.entrypoint, None);
+        assert_eq!(diff.added_functions, vec!["extra"]);
+        assert_eq!(diff.removed_functions, vec!["legacy"]);
+        assert_eq!(diff.changed_functions.len(), 2);
+
+        let helper = &diff.changed_functions[0];
+        assert_eq!(helper.name, "helper");
+        assert_eq!(
+            helper.resources.as_ref().map(|change| &change.to.gpus),
+            Some(&vec!["A100:1".to_string()])
+        );
+        assert_eq!(helper.timeout_sec, None);
+        assert!(helper.other_changes);
+
+        let main = &diff.changed_functions[1];
+        assert_eq!(main.name, "main");
+        assert_eq!(main.resources, None);
+        assert_eq!(main.timeout_sec, Some(Change { from: 60, to: 120 }));
+        assert!(!main.other_changes);
+    }
+
+    #[test]
+    fn test_api_functions() {
+        let function = |name: &str, is_api: bool| ApplicationFunction {
+            name: name.to_string(),
+            is_api,
+            ..Default::default()
+        };
+        let mut app = Application {
+            entrypoint: EntryPointManifest {
+                function_name: "main".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        for function in [
+            function("main", false),
+            function("summarize", true),
+            function("helper", false),
+        ] {
+            app.functions.insert(function.name.clone(), function);
+        }
+
+        let mut names = app
+            .api_functions()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["summarize"]);
+        assert_eq!(app.entrypoint_function().unwrap().name, "main");
+    }
+
+    #[test]
+    fn test_identifiers_in_builders() {
+        let request_id = RequestId::from("request-123");
+        let request = GetRequestRequest::builder()
+            .namespace("default".into())
+            .application(ApplicationName::from(String::from("my-app")))
+            .request_id(request_id.clone())
+            .build()
+            .unwrap();
+        assert_eq!(request.namespace, "default");
+        assert_eq!(request.application.as_str(), "my-app");
+        assert_eq!(request.request_id, request_id);
+        assert_eq!(request.request_id.to_string(), "request-123");
+        assert_eq!(
+            serde_json::to_value(&request_id).unwrap(),
+            json!("request-123")
+        );
+
+        let error = GetRequestRequest::builder()
+            .namespace(Namespace::from(" "))
+            .application("my-app".into())
+            .request_id("request-123".into())
+            .build()
+            .unwrap_err();
+        assert!(error.to_string().contains("namespace"));
+    }
 }
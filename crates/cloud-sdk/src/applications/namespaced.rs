@@ -0,0 +1,290 @@
+//! Applications client bound to a single namespace.
+
+use std::collections::HashMap;
+
+use super::{ApplicationsClient, error::ApplicationsError, models};
+use crate::{bulk::BulkResult, error::SdkError, validation::validate_identifier};
+
+/// An [`ApplicationsClient`] bound to a namespace, created with
+/// [`ApplicationsClient::for_namespace`].
+///
+/// Its methods mirror the ones of [`ApplicationsClient`] without the namespace,
+/// and use the default options of the underlying requests. Use
+/// [`NamespacedApplicationsClient::inner`] to set other options.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use tensorlake_cloud_sdk::Sdk;
+///
+/// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+///     let sdk = Sdk::new("https://api.tensorlake.ai", "your-api-key")?;
+///     let apps_client = sdk.applications().for_namespace(&"default".into())?;
+///
+///     for app in apps_client.list().await?.applications {
+///         println!("{}", app.name);
+///     }
+///     let request_id = apps_client
+///         .invoke(&"my-app".into(), serde_json::json!({"input": "hello"}))
+///         .await?
+///         .into_request_id()?;
+///     let request = apps_client.get_request(&"my-app".into(), &request_id).await?;
+///     println!("Outcome: {:?}", request.outcome);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone)]
+pub struct NamespacedApplicationsClient {
+    client: ApplicationsClient,
+    namespace: models::Namespace,
+}
+
+impl NamespacedApplicationsClient {
+    pub(super) fn new(
+        client: ApplicationsClient,
+        namespace: &models::Namespace,
+    ) -> Result<Self, SdkError> {
+        validate_identifier("namespace", Some(namespace))
+            .map_err(ApplicationsError::InvalidRequest)?;
+        Ok(Self {
+            client,
+            namespace: namespace.clone(),
+        })
+    }
+
+    /// The namespace the client is bound to.
+    pub fn namespace(&self) -> &models::Namespace {
+        &self.namespace
+    }
+
+    /// The underlying client, which isn't bound to a namespace.
+    pub fn inner(&self) -> &ApplicationsClient {
+        &self.client
+    }
+
+    /// List the applications of the namespace. See [`ApplicationsClient::list`].
+    pub async fn list(&self) -> Result<models::ApplicationsList, SdkError> {
+        self.client
+            .list(
+                &models::ListApplicationsRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Get an application. See [`ApplicationsClient::get`].
+    pub async fn get(
+        &self,
+        application: &models::ApplicationName,
+    ) -> Result<models::Application, SdkError> {
+        self.client
+            .get(
+                &models::GetApplicationRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Get several applications concurrently. See [`ApplicationsClient::get_many`].
+    pub async fn get_many(
+        &self,
+        names: &[models::ApplicationName],
+        concurrency: usize,
+    ) -> Result<BulkResult<models::Application>, SdkError> {
+        self.client
+            .get_many(&self.namespace, names, concurrency)
+            .await
+    }
+
+    /// Get the entrypoint schema of an application. See [`ApplicationsClient::get_schema`].
+    pub async fn get_schema(
+        &self,
+        application: &models::ApplicationName,
+    ) -> Result<models::EntryPointManifest, SdkError> {
+        self.client.get_schema(&self.namespace, application).await
+    }
+
+    /// Create or update an application. See [`ApplicationsClient::upsert`].
+    pub async fn upsert(
+        &self,
+        application_manifest: models::ApplicationManifest,
+        code_zip: Vec<u8>,
+    ) -> Result<(), SdkError> {
+        self.client
+            .upsert(
+                &models::UpsertApplicationRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application_manifest(application_manifest)
+                    .code_zip(code_zip)
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Delete an application. See [`ApplicationsClient::delete`].
+    pub async fn delete(&self, application: &models::ApplicationName) -> Result<(), SdkError> {
+        self.client
+            .delete(
+                &models::DeleteApplicationRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Invoke an application. See [`ApplicationsClient::invoke`].
+    pub async fn invoke(
+        &self,
+        application: &models::ApplicationName,
+        body: serde_json::Value,
+    ) -> Result<models::InvokeResponse, SdkError> {
+        self.client
+            .invoke(
+                &models::InvokeApplicationRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .body(body)
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Invoke an application with named arguments. See [`ApplicationsClient::invoke_with_args`].
+    pub async fn invoke_with_args(
+        &self,
+        application: &models::ApplicationName,
+        args: HashMap<String, serde_json::Value>,
+    ) -> Result<models::InvokeResponse, SdkError> {
+        self.client
+            .invoke_with_args(&self.namespace, application, args)
+            .await
+    }
+
+    /// Invoke an application with multiple inputs concurrently.
+    /// See [`ApplicationsClient::invoke_batch`].
+    pub async fn invoke_batch(
+        &self,
+        application: &models::ApplicationName,
+        bodies: Vec<serde_json::Value>,
+        concurrency: usize,
+    ) -> Result<BulkResult<models::RequestId>, SdkError> {
+        self.client
+            .invoke_batch(&self.namespace, application, bodies, concurrency)
+            .await
+    }
+
+    /// List the requests of an application. See [`ApplicationsClient::list_requests`].
+    pub async fn list_requests(
+        &self,
+        application: &models::ApplicationName,
+    ) -> Result<models::ApplicationRequests, SdkError> {
+        self.client
+            .list_requests(
+                &models::ListRequestsRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Get a request. See [`ApplicationsClient::get_request`].
+    pub async fn get_request(
+        &self,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+    ) -> Result<models::Request, SdkError> {
+        self.client
+            .get_request(
+                &models::GetRequestRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .request_id(request_id.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Delete a request. See [`ApplicationsClient::delete_request`].
+    pub async fn delete_request(
+        &self,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+    ) -> Result<(), SdkError> {
+        self.client
+            .delete_request(
+                &models::DeleteRequestRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .request_id(request_id.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Get a function run of a request. See [`ApplicationsClient::get_function_run`].
+    pub async fn get_function_run(
+        &self,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+        function_run_id: &models::FunctionCallId,
+    ) -> Result<models::FunctionRun, SdkError> {
+        self.client
+            .get_function_run(
+                &models::GetFunctionRunRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .request_id(request_id.clone())
+                    .function_run_id(function_run_id.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Download the output of a request. See [`ApplicationsClient::download_request_output`].
+    pub async fn download_request_output(
+        &self,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+    ) -> Result<models::DownloadOutput, SdkError> {
+        self.client
+            .download_request_output(
+                &models::DownloadRequestOutputRequest::builder()
+                    .namespace(self.namespace.clone())
+                    .application(application.clone())
+                    .request_id(request_id.clone())
+                    .build()
+                    .map_err(invalid_request)?,
+            )
+            .await
+    }
+
+    /// Watch the progress of a request. See [`ApplicationsClient::watch_progress`].
+    pub fn watch_progress(
+        &self,
+        application: &models::ApplicationName,
+        request_id: &models::RequestId,
+        backoff: models::BackoffPolicy,
+    ) -> models::ProgressUpdatesStream {
+        self.client
+            .watch_progress(&self.namespace, application, request_id, backoff)
+    }
+}
+
+/// Report an argument rejected by a request builder.
+fn invalid_request(error: impl std::fmt::Display) -> SdkError {
+    ApplicationsError::InvalidRequest(error.to_string()).into()
+}
@@ -0,0 +1,129 @@
+//! # Bulk Results
+//!
+//! The results of the bulk methods of the SDK, such as
+//! [`ApplicationsClient::get_many`](crate::applications::ApplicationsClient::get_many).
+use crate::error::SdkError;
+
+/// The result of each item of a bulk operation, in the order of the items.
+///
+/// A failed item doesn't stop the others, so the successful results are kept
+/// even when some items fail. Use [`BulkResult::into_result_if_all_ok`] to
+/// treat any failure as a failure of the whole operation instead.
+#[derive(Debug)]
+pub struct BulkResult<T> {
+    results: Vec<Result<T, SdkError>>,
+}
+
+impl<T> BulkResult<T> {
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether there are no items.
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The result of each item.
+    pub fn iter(&self) -> std::slice::Iter<'_, Result<T, SdkError>> {
+        self.results.iter()
+    }
+
+    /// The values of the items that succeeded.
+    pub fn successes(&self) -> impl Iterator<Item = &T> {
+        self.results
+            .iter()
+            .filter_map(|result| result.as_ref().ok())
+    }
+
+    /// The errors of the items that failed, with the index of each item.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, &SdkError)> {
+        self.results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, result)| result.as_ref().err().map(|error| (index, error)))
+    }
+
+    /// Whether every item succeeded.
+    pub fn is_all_ok(&self) -> bool {
+        self.results.iter().all(Result::is_ok)
+    }
+
+    /// The values of all the items, or the error of the first item that failed.
+    pub fn into_result_if_all_ok(self) -> Result<Vec<T>, SdkError> {
+        self.results.into_iter().collect()
+    }
+
+    /// The result of each item.
+    pub fn into_results(self) -> Vec<Result<T, SdkError>> {
+        self.results
+    }
+}
+
+impl<T> From<Vec<Result<T, SdkError>>> for BulkResult<T> {
+    fn from(results: Vec<Result<T, SdkError>>) -> Self {
+        Self { results }
+    }
+}
+
+impl<T> FromIterator<Result<T, SdkError>> for BulkResult<T> {
+    fn from_iter<I: IntoIterator<Item = Result<T, SdkError>>>(iter: I) -> Self {
+        Self {
+            results: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> IntoIterator for BulkResult<T> {
+    type Item = Result<T, SdkError>;
+    type IntoIter = std::vec::IntoIter<Result<T, SdkError>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BulkResult<T> {
+    type Item = &'a Result<T, SdkError>;
+    type IntoIter = std::slice::Iter<'a, Result<T, SdkError>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.results.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulk_result() {
+        let results: BulkResult<i32> = vec![
+            Ok(1),
+            Err(SdkError::Timeout),
+            Ok(3),
+            Err(SdkError::ClientError("boom".to_string())),
+        ]
+        .into();
+
+        assert_eq!(results.len(), 4);
+        assert!(!results.is_all_ok());
+        assert_eq!(results.successes().copied().collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(
+            results
+                .failures()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert!(matches!(
+            results.into_result_if_all_ok(),
+            Err(SdkError::Timeout)
+        ));
+
+        let results: BulkResult<i32> = vec![Ok(1), Ok(2)].into_iter().collect();
+        assert!(results.is_all_ok());
+        assert_eq!(results.into_result_if_all_ok().unwrap(), vec![1, 2]);
+    }
+}
@@ -1,36 +1,82 @@
 //! HTTP client that interacts with the Tensorlake Cloud API.
+use bytes::Bytes;
 use futures::{Stream, StreamExt};
+use http::Extensions;
 use reqwest::{
     Method, Request, Response, StatusCode,
-    header::{ACCEPT, HeaderMap, HeaderValue, InvalidHeaderValue},
+    header::{
+        ACCEPT, CONTENT_ENCODING, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue,
+        InvalidHeaderValue,
+    },
 };
 use reqwest_eventsource::{CannotCloneRequestError, Error as SseError, Event, EventSource};
 use reqwest_middleware::{ClientBuilder as ReqwestClientBuilder, ClientWithMiddleware, Middleware};
 use serde::de::DeserializeOwned;
-use std::{pin::Pin, result::Result, sync::Arc};
+use std::{
+    io::Read,
+    pin::Pin,
+    result::Result,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
+};
 
-use crate::error::SdkError;
+use crate::{
+    coalesce::GetCoalescer,
+    error::SdkError,
+    etag_cache::EtagCache,
+    metrics::{RequestMetrics, RequestMetricsEvent, RequestMetricsHook, RouteTemplate},
+};
 
 /// HTTP client that interacts with the Tensorlake Cloud API.
 #[derive(Clone)]
 pub struct Client {
     /// Base URL of the API, used to construct the full URL for each request.
     base_url: String,
+    /// Base URL of the image build service, if it's not served from `base_url`.
+    build_service_url: Option<String>,
     /// Base client to construct more specialized clients, used to construct EventSource requests.
     base_client: reqwest::Client,
     /// Client with user provided middlewares. Used to perform regular HTTP requests.
     client: ClientWithMiddleware,
+    /// Organization ID the client is scoped to, if any.
+    organization_id: Option<String>,
+    /// Project ID the client is scoped to, if any.
+    project_id: Option<String>,
+    /// Shares responses between concurrent identical GET requests, if enabled.
+    coalescer: Option<GetCoalescer>,
+    /// API version reported by the server in its last response, if any.
+    server_api_version: Arc<Mutex<Option<String>>>,
+    /// Maximum number of bytes read from error response bodies, if limited.
+    max_error_body_bytes: Option<usize>,
 }
 
+/// Response header in which the server reports its API version.
+pub const SERVER_API_VERSION_HEADER: &str = "X-Tensorlake-Api-Version";
+
 /// Builder for creating a [`Client`] with a fluent API.
 ///
 /// The base URL is required, while bearer token, middlewares, and scope are optional.
 pub struct ClientBuilder {
     base_url: String,
+    base_path: String,
+    build_service_url: Option<String>,
     bearer_token: Option<String>,
+    coalesce_gets: bool,
+    etag_cache_capacity: Option<usize>,
+    max_error_body_bytes: Option<usize>,
     middlewares: Vec<Arc<dyn Middleware + 'static>>,
+    on_request_complete: Option<RequestMetricsHook>,
     organization_id: Option<String>,
     project_id: Option<String>,
+    proxy: Option<String>,
+    no_proxy: bool,
+    redirect_policy: RedirectPolicy,
+    user_agent: Option<String>,
+    #[cfg(feature = "dangerous-tls")]
+    accept_invalid_certs: bool,
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    root_certificates: Vec<Vec<u8>>,
 }
 
 impl ClientBuilder {
@@ -42,10 +88,24 @@ impl ClientBuilder {
     pub fn new(base_url: &str) -> Self {
         Self {
             base_url: base_url.to_string(),
+            base_path: String::new(),
+            build_service_url: None,
             bearer_token: None,
+            coalesce_gets: false,
+            etag_cache_capacity: None,
+            max_error_body_bytes: None,
             middlewares: Vec::new(),
+            on_request_complete: None,
             organization_id: None,
             project_id: None,
+            proxy: None,
+            no_proxy: false,
+            redirect_policy: RedirectPolicy::default(),
+            user_agent: None,
+            #[cfg(feature = "dangerous-tls")]
+            accept_invalid_certs: false,
+            #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+            root_certificates: Vec::new(),
         }
     }
 
@@ -70,6 +130,20 @@ impl ClientBuilder {
         self
     }
 
+    /// Call `hook` after every HTTP call, with its method, path, status, and duration.
+    ///
+    /// Use it to feed the calls of the SDK into metrics systems such as Prometheus
+    /// or OpenTelemetry. The hook sees the calls as sent by the SDK, before the
+    /// middlewares added with [`ClientBuilder::middleware`]. Server-sent event
+    /// streams aren't reported.
+    pub fn on_request_complete(
+        mut self,
+        hook: impl Fn(&RequestMetricsEvent) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_request_complete = Some(Arc::new(hook));
+        self
+    }
+
     /// Set the organization and project scope.
     pub fn scope(mut self, organization_id: &str, project_id: &str) -> Self {
         self.organization_id = Some(organization_id.to_string());
@@ -77,6 +151,132 @@ impl ClientBuilder {
         self
     }
 
+    /// Prepend a path prefix to the path of every request, for deployments that
+    /// serve the API under a prefix behind a reverse proxy.
+    ///
+    /// With `base_path("/tensorlake")`, `/v1/namespaces/...` is sent to
+    /// `<base_url>/tensorlake/v1/namespaces/...`. Leading and trailing slashes are
+    /// optional. The prefix isn't applied to [`ClientBuilder::build_service_url`].
+    pub fn base_path(mut self, base_path: &str) -> Self {
+        let base_path = base_path.trim_matches('/');
+        self.base_path = if base_path.is_empty() {
+            String::new()
+        } else {
+            format!("/{base_path}")
+        };
+        self
+    }
+
+    /// Send image build requests to a separate build service instead of the base URL.
+    ///
+    /// The image endpoints are resolved against this URL, for example
+    /// `https://builds.example.com/images/v2/builds`.
+    pub fn build_service_url(mut self, url: &str) -> Self {
+        self.build_service_url = Some(url.to_string());
+        self
+    }
+
+    /// Coalesce concurrent identical GET requests into a single request.
+    ///
+    /// While a GET request is in flight, identical requests, with the same URL and
+    /// headers, wait for it and receive a copy of its response instead of being sent.
+    /// Coalesced responses are read in full before being returned.
+    pub fn coalesce_gets(mut self, coalesce_gets: bool) -> Self {
+        self.coalesce_gets = coalesce_gets;
+        self
+    }
+
+    /// Cache up to `capacity` GET responses in memory, revalidating them with the server.
+    ///
+    /// JSON responses with an `ETag` or `Last-Modified` header are cached, and sent
+    /// back with `If-None-Match` or `If-Modified-Since`. When the server answers
+    /// `304 Not Modified`, the cached response is returned instead. Streamed
+    /// responses, such as logs and downloads without a `Content-Length`, are not
+    /// cached. When the cache is full, the least recently used response is evicted.
+    pub fn enable_etag_cache(mut self, capacity: usize) -> Self {
+        self.etag_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Read at most `max_bytes` of the body of error responses.
+    ///
+    /// The body of an error response becomes the message of its [`SdkError`]. By
+    /// default it's read in full, which is slow for large error pages. With a
+    /// limit, only the beginning of the body is read, and the connection is
+    /// closed without reading the rest.
+    pub fn truncate_error_bodies(mut self, max_bytes: usize) -> Self {
+        self.max_error_body_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Route all HTTP and HTTPS traffic through the proxy at the given URL.
+    ///
+    /// An invalid proxy URL is reported as [`SdkError::ClientError`] when the client is built.
+    pub fn proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.to_string());
+        self.no_proxy = false;
+        self
+    }
+
+    /// Disable all proxies, including the ones configured through environment variables.
+    pub fn no_proxy(mut self) -> Self {
+        self.proxy = None;
+        self.no_proxy = true;
+        self
+    }
+
+    /// Set how redirects are followed. Defaults to following up to 10 redirects.
+    ///
+    /// Whatever the policy, the `Authorization` header is removed when a redirect
+    /// leads to another host or port, so the bearer token isn't sent to it.
+    pub fn redirect_policy(mut self, redirect_policy: RedirectPolicy) -> Self {
+        self.redirect_policy = redirect_policy;
+        self
+    }
+
+    /// Replace the user agent sent with every request.
+    ///
+    /// Prefer [`ClientBuilder::append_user_agent`] to keep the SDK version in the user agent.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Identify your tool in the user agent, keeping the SDK version token.
+    ///
+    /// For example, `append_user_agent("my-cli/2.1")` sends
+    /// `my-cli/2.1 (Tensorlake Cloud SDK/<version>)`.
+    pub fn append_user_agent(mut self, product: &str) -> Self {
+        self.user_agent = Some(format!("{product} ({})", default_user_agent()));
+        self
+    }
+
+    /// Accept invalid TLS certificates, including self-signed and expired ones.
+    ///
+    /// # Warning
+    ///
+    /// **This disables certificate verification entirely.** Any certificate will be
+    /// trusted, which makes the client vulnerable to man-in-the-middle attacks.
+    /// Only use it against internal or staging endpoints, never in production.
+    /// Prefer adding the endpoint's CA certificate when possible.
+    ///
+    /// This method is only available with the `dangerous-tls` feature.
+    #[cfg(feature = "dangerous-tls")]
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Trust an additional root CA certificate, in PEM format.
+    ///
+    /// Use this to connect to deployments with certificates issued by a private CA.
+    /// An invalid certificate is reported as [`SdkError::ClientError`] when the client is built.
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    pub fn add_root_certificate(mut self, pem: &[u8]) -> Self {
+        self.root_certificates.push(pem.to_vec());
+        self
+    }
+
     /// Build the [`Client`].
     ///
     /// # Errors
@@ -98,29 +298,172 @@ impl ClientBuilder {
             default_headers.insert("X-Tensorlake-Project-Id", str_to_header_value(project_id)?);
         }
 
-        let base_client = new_base_client(&default_headers)?;
+        let base_client = new_base_client(&self, &default_headers)?;
         let mut builder = ReqwestClientBuilder::new(base_client.clone());
 
+        if let Some(hook) = &self.on_request_complete {
+            builder = builder.with(RequestMetrics::new(hook.clone()));
+        }
+
         for middleware in &self.middlewares {
             builder = builder.with_arc(middleware.clone());
         }
 
+        if let Some(capacity) = self.etag_cache_capacity {
+            builder = builder.with(EtagCache::new(capacity));
+        }
+
         let client = builder.build();
 
         Ok(Client {
-            base_url: self.base_url,
+            base_url: if self.base_path.is_empty() {
+                self.base_url
+            } else {
+                self.base_url.trim_end_matches('/').to_string() + &self.base_path
+            },
+            build_service_url: self.build_service_url,
             base_client,
             client,
+            organization_id: self.organization_id,
+            project_id: self.project_id,
+            coalescer: self.coalesce_gets.then(GetCoalescer::default),
+            server_api_version: Arc::default(),
+            max_error_body_bytes: self.max_error_body_bytes,
         })
     }
 }
 
-type EventSourceStream<T> = Pin<Box<dyn Stream<Item = Result<T, SdkError>> + Send>>;
+/// How the client follows redirects, set with [`ClientBuilder::redirect_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedirectPolicy {
+    /// Don't follow redirects. Redirect responses are returned as
+    /// [`SdkError::ServerError`] with their status.
+    None,
+    /// Follow up to this many redirects in a row.
+    Limited(usize),
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        RedirectPolicy::Limited(10)
+    }
+}
+
+impl RedirectPolicy {
+    fn to_reqwest(self) -> reqwest::redirect::Policy {
+        match self {
+            RedirectPolicy::None => reqwest::redirect::Policy::none(),
+            RedirectPolicy::Limited(max) => reqwest::redirect::Policy::limited(max),
+        }
+    }
+}
+
+/// A stream of server-sent events.
+///
+/// Dropping the stream closes the underlying connection. Use [`EventStream::close`]
+/// to close it while keeping the stream around, for example when it's borrowed.
+pub struct EventStream<T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, SdkError>> + Send>>,
+}
+
+impl<T: Send + 'static> EventStream<T> {
+    pub(crate) fn new(stream: impl Stream<Item = Result<T, SdkError>> + Send + 'static) -> Self {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+
+    /// Close the stream, aborting the underlying request.
+    ///
+    /// The connection is released immediately, and polling the stream afterwards
+    /// returns `None`.
+    pub fn close(&mut self) {
+        self.inner = Box::pin(futures::stream::empty());
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<T, SdkError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
 
 impl Client {
+    /// The organization ID set with [`ClientBuilder::scope`], if any.
+    pub fn organization_id(&self) -> Option<&str> {
+        self.organization_id.as_deref()
+    }
+
+    /// The project ID set with [`ClientBuilder::scope`], if any.
+    pub fn project_id(&self) -> Option<&str> {
+        self.project_id.as_deref()
+    }
+
+    /// The image build service URL set with [`ClientBuilder::build_service_url`], if any.
+    pub fn build_service_url(&self) -> Option<&str> {
+        self.build_service_url.as_deref()
+    }
+
+    /// The API version reported by the server in the last response it sent, if any.
+    ///
+    /// The server reports it in the [`SERVER_API_VERSION_HEADER`] header. Compare
+    /// it with the version the SDK was written against to diagnose calls that
+    /// misbehave because of a version skew. Clones of the client share it.
+    pub fn server_api_version(&self) -> Option<String> {
+        self.server_api_version
+            .lock()
+            .expect("server API version lock poisoned")
+            .clone()
+    }
+
+    /// A copy of this client that sends requests to a different base URL.
+    pub(crate) fn with_base_url(&self, base_url: &str) -> Client {
+        Client {
+            base_url: base_url.to_string(),
+            ..self.clone()
+        }
+    }
+
     /// Execute an HTTP request.
     pub async fn execute(&self, request: Request) -> Result<Response, SdkError> {
-        let response = self.client.execute(request).await?;
+        self.execute_with_extensions(request, Extensions::new())
+            .await
+    }
+
+    /// Execute an HTTP request, tagged with the route template of its path.
+    ///
+    /// The template, such as `/v1/namespaces/{namespace}/applications/{application}`,
+    /// is reported to the [`ClientBuilder::on_request_complete`] hook, and set as a
+    /// [`RouteTemplate`] extension for middlewares.
+    pub async fn execute_route(
+        &self,
+        request: Request,
+        route: &'static str,
+    ) -> Result<Response, SdkError> {
+        let mut extensions = Extensions::new();
+        extensions.insert(RouteTemplate(route));
+        self.execute_with_extensions(request, extensions).await
+    }
+
+    async fn execute_with_extensions(
+        &self,
+        request: Request,
+        mut extensions: Extensions,
+    ) -> Result<Response, SdkError> {
+        let response = match &self.coalescer {
+            Some(coalescer) => {
+                coalescer
+                    .execute(&self.client, request, &extensions)
+                    .await?
+            }
+            None => {
+                self.client
+                    .execute_with_extensions(request, &mut extensions)
+                    .await?
+            }
+        };
         self.handle_response(response).await
     }
 
@@ -135,11 +478,26 @@ impl Client {
     pub async fn build_event_source_request<T>(
         &self,
         path: &str,
-    ) -> Result<EventSourceStream<T>, CannotCloneRequestError>
+    ) -> Result<EventStream<T>, CannotCloneRequestError>
     where
-        T: DeserializeOwned,
+        T: DeserializeOwned + Send + 'static,
     {
-        let builder = self.base_client.get(self.base_url.clone() + path);
+        self.build_event_source_request_with_query(path, &[]).await
+    }
+
+    /// Like [`Client::build_event_source_request`], with query parameters added to the URL.
+    pub async fn build_event_source_request_with_query<T>(
+        &self,
+        path: &str,
+        query: &[(&str, &str)],
+    ) -> Result<EventStream<T>, CannotCloneRequestError>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let builder = self
+            .base_client
+            .get(self.base_url.clone() + path)
+            .query(query);
         let req = EventSource::new(builder)?;
 
         let stream = req
@@ -163,7 +521,7 @@ impl Client {
                     }
                 }
             });
-        Ok(Box::pin(stream))
+        Ok(EventStream::new(stream))
     }
 
     pub fn build_multipart_request(
@@ -206,28 +564,218 @@ impl Client {
         &self,
         response: reqwest::Response,
     ) -> Result<reqwest::Response, SdkError> {
+        if let Some(version) = response
+            .headers()
+            .get(SERVER_API_VERSION_HEADER)
+            .and_then(|version| version.to_str().ok())
+        {
+            *self
+                .server_api_version
+                .lock()
+                .expect("server API version lock poisoned") = Some(version.to_string());
+        }
+
         let status = response.status();
 
         match status {
             StatusCode::UNAUTHORIZED => {
-                let message = body_message_or_default(response, "Unauthorized").await;
+                let message = self.error_message(response, "Unauthorized").await;
                 Err(SdkError::Authentication(message))
             }
             StatusCode::FORBIDDEN => {
-                let message = body_message_or_default(response, "Forbidden").await;
+                let message = self.error_message(response, "Forbidden").await;
                 Err(SdkError::Authorization(message))
             }
+            StatusCode::PRECONDITION_FAILED => {
+                let message = self.error_message(response, "Precondition failed").await;
+                Err(SdkError::PreconditionFailed(message))
+            }
             status if status.is_server_error() => {
-                let message = body_message_or_default(response, "Server error").await;
+                let message = self.error_message(response, "Server error").await;
                 Err(SdkError::ServerError { status, message })
             }
             status if !status.is_success() => {
-                let message = body_message_or_default(response, "Request failed").await;
+                let message = self.error_message(response, "Request failed").await;
                 Err(SdkError::ServerError { status, message })
             }
             _ => Ok(response),
         }
     }
+
+    /// The message of an error response, read from its body, or `default` if
+    /// the body is empty or can't be read.
+    async fn error_message(&self, mut response: Response, default: &str) -> String {
+        let Some(max_bytes) = self.max_error_body_bytes else {
+            return body_message_or_default(response, default).await;
+        };
+
+        let mut body = Vec::new();
+        while body.len() < max_bytes {
+            match response.chunk().await {
+                Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+                Ok(None) => break,
+                Err(_) => return default.to_string(),
+            }
+        }
+        body.truncate(max_bytes);
+
+        // Don't split the last character if the limit falls in the middle of it.
+        let text = match std::str::from_utf8(&body) {
+            Ok(text) => text,
+            Err(error) if error.error_len().is_none() => {
+                std::str::from_utf8(&body[..error.valid_up_to()]).unwrap_or_default()
+            }
+            Err(_) => return String::from_utf8_lossy(&body).into_owned(),
+        };
+        if text.is_empty() {
+            default.to_string()
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// Run a call, failing with [`SdkError::Timeout`] if it doesn't complete within `timeout`.
+pub(crate) async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    call: impl Future<Output = Result<T, SdkError>>,
+) -> Result<T, SdkError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, call)
+            .await
+            .map_err(|_| SdkError::Timeout)?,
+        None => call.await,
+    }
+}
+
+/// Percent-encode a value interpolated into a URL path, so that names containing
+/// slashes, spaces or other reserved characters stay within their segment.
+pub(crate) fn urlencode(segment: &str) -> String {
+    urlencoding::encode(segment).into_owned()
+}
+
+/// Whether a `Content-Type` value is JSON, like `application/json` or
+/// `application/problem+json`.
+pub(crate) fn is_json_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "application/json" || mime.ends_with("+json")
+}
+
+/// Deserialize a JSON response body.
+///
+/// Responses that declare a content type other than JSON, like the HTML error
+/// page of a misconfigured gateway, are rejected with
+/// [`SdkError::UnexpectedResponse`] instead of failing deep inside the JSON parser.
+pub(crate) async fn deserialize_json<T: DeserializeOwned>(
+    response: Response,
+) -> Result<T, SdkError> {
+    if let Some(content_type) = response.headers().get(CONTENT_TYPE) {
+        let content_type = content_type.to_str().unwrap_or_default();
+        if !is_json_content_type(content_type) {
+            return Err(SdkError::UnexpectedResponse {
+                detail: format!("expected a JSON response, got content type `{content_type}`"),
+            });
+        }
+    }
+
+    let path = response.url().path().to_string();
+    let bytes = response.bytes().await?;
+    if bytes.trim_ascii().is_empty() {
+        return Err(SdkError::UnexpectedResponse {
+            detail: format!("empty response body from `{path}`"),
+        });
+    }
+    let jd = &mut serde_json::Deserializer::from_slice(&bytes);
+    Ok(serde_path_to_error::deserialize(jd)?)
+}
+
+/// Maximum size of a decoded download, so that a small compressed response can't
+/// inflate into an unbounded amount of memory.
+const MAX_DECODED_BODY_BYTES: u64 = 1 << 30;
+
+/// Read the body of a download, undoing the `Content-Encoding` of the response.
+///
+/// `gzip` and `deflate` are decoded. Other encodings, like `br`, are rejected with
+/// [`SdkError::UnexpectedResponse`] instead of returning compressed bytes as if
+/// they were the content, and so is content that decodes to more than 1 GiB.
+/// Returns the content, and whether it was decoded.
+pub(crate) async fn read_decoded_body(response: Response) -> Result<(Bytes, bool), SdkError> {
+    let encodings = content_encodings(&response)?;
+    let mut content = response.bytes().await?;
+    let decoded = !encodings.is_empty();
+    for encoding in encodings.iter().rev() {
+        content = decode_content(&content, encoding, MAX_DECODED_BODY_BYTES)?;
+    }
+    Ok((content, decoded))
+}
+
+/// Decode content with a `gzip` or `deflate` coding, up to `max_bytes` of decoded content.
+fn decode_content(content: &[u8], encoding: &str, max_bytes: u64) -> Result<Bytes, SdkError> {
+    let reader: Box<dyn Read + '_> = match encoding {
+        "gzip" | "x-gzip" => Box::new(flate2::read::GzDecoder::new(content)),
+        _ => Box::new(flate2::read::ZlibDecoder::new(content)),
+    };
+    let mut decoded = Vec::new();
+    reader
+        .take(max_bytes + 1)
+        .read_to_end(&mut decoded)
+        .map_err(|error| SdkError::UnexpectedResponse {
+            detail: format!("invalid `{encoding}` content: {error}"),
+        })?;
+    if decoded.len() as u64 > max_bytes {
+        return Err(SdkError::UnexpectedResponse {
+            detail: format!("`{encoding}` content decodes to more than {max_bytes} bytes"),
+        });
+    }
+    Ok(decoded.into())
+}
+
+/// The content codings of a response, in the order they were applied, without
+/// `identity`.
+///
+/// Returns [`SdkError::UnexpectedResponse`] for codings that can't be decoded.
+pub(crate) fn content_encodings(response: &Response) -> Result<Vec<String>, SdkError> {
+    let mut encodings = Vec::new();
+    for header in response.headers().get_all(CONTENT_ENCODING) {
+        let header = header.to_str().map_err(|_| SdkError::UnexpectedResponse {
+            detail: "invalid content encoding header".to_string(),
+        })?;
+        for encoding in header
+            .split(',')
+            .map(|encoding| encoding.trim().to_ascii_lowercase())
+        {
+            match encoding.as_str() {
+                "" | "identity" => {}
+                "gzip" | "x-gzip" | "deflate" => encodings.push(encoding),
+                _ => {
+                    return Err(SdkError::UnexpectedResponse {
+                        detail: format!("unsupported content encoding `{encoding}`"),
+                    });
+                }
+            }
+        }
+    }
+    Ok(encodings)
+}
+
+/// Add the headers of a single call to a request.
+pub(crate) fn insert_headers(
+    request: &mut Request,
+    headers: &[(String, String)],
+) -> Result<(), SdkError> {
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|error| SdkError::InvalidHeaderValue(error.to_string()))?;
+        request
+            .headers_mut()
+            .append(name, str_to_header_value(value)?);
+    }
+    Ok(())
 }
 
 async fn body_message_or_default(response: Response, default: &str) -> String {
@@ -257,13 +805,453 @@ fn str_to_header_value(value: &str) -> Result<HeaderValue, SdkError> {
         .map_err(|e: InvalidHeaderValue| SdkError::InvalidHeaderValue(e.to_string()))
 }
 
-fn new_base_client(headers: &HeaderMap) -> Result<reqwest::Client, SdkError> {
-    let client = reqwest::Client::builder()
-        .user_agent(format!(
-            "Tensorlake Cloud SDK/{}",
-            env!("CARGO_PKG_VERSION")
-        ))
+fn default_user_agent() -> String {
+    format!("Tensorlake Cloud SDK/{}", env!("CARGO_PKG_VERSION"))
+}
+
+fn new_base_client(
+    config: &ClientBuilder,
+    headers: &HeaderMap,
+) -> Result<reqwest::Client, SdkError> {
+    let user_agent = config.user_agent.clone().unwrap_or_else(default_user_agent);
+    let mut builder = reqwest::Client::builder()
+        .user_agent(user_agent)
         .default_headers(headers.clone())
-        .build()?;
+        .redirect(config.redirect_policy.to_reqwest());
+
+    if config.no_proxy {
+        builder = builder.no_proxy();
+    }
+    if let Some(url) = &config.proxy {
+        let proxy = reqwest::Proxy::all(url)
+            .map_err(|e| SdkError::ClientError(format!("Invalid proxy URL {url}: {e}")))?;
+        builder = builder.proxy(proxy);
+    }
+
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    for pem in &config.root_certificates {
+        let certificates = reqwest::Certificate::from_pem_bundle(pem)
+            .map_err(|e| SdkError::ClientError(format!("Invalid root certificate: {e}")))?;
+        if certificates.is_empty() {
+            return Err(SdkError::ClientError(
+                "Invalid root certificate: no PEM certificate found".to_string(),
+            ));
+        }
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    #[cfg(feature = "dangerous-tls")]
+    if config.accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    // Prefer native-tls when it's explicitly enabled, since rustls is on by default.
+    #[cfg(feature = "native-tls")]
+    let builder = builder.use_native_tls();
+    #[cfg(all(feature = "rustls-tls", not(feature = "native-tls")))]
+    let builder = builder.use_rustls_tls();
+
+    let client = builder.build()?;
     Ok(client)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::*;
+
+    #[tokio::test]
+    async fn test_event_stream_close() {
+        let mut stream = EventStream::new(futures::stream::iter(vec![Ok(1), Ok(2)]));
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+
+        stream.close();
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    fn test_build_service_url() {
+        let client = ClientBuilder::new("https://api.tensorlake.ai")
+            .build()
+            .unwrap();
+        assert_eq!(client.build_service_url(), None);
+
+        let client = ClientBuilder::new("https://api.tensorlake.ai")
+            .build_service_url("https://builds.tensorlake.ai")
+            .build()
+            .unwrap();
+        assert_eq!(
+            client.build_service_url(),
+            Some("https://builds.tensorlake.ai")
+        );
+
+        let request = client
+            .with_base_url("https://builds.tensorlake.ai")
+            .request(Method::GET, "/images/v2/builds")
+            .build()
+            .unwrap();
+        assert_eq!(
+            request.url().as_str(),
+            "https://builds.tensorlake.ai/images/v2/builds"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_gets() {
+        let server = serve_with(usize::MAX, |_| {
+            std::thread::sleep(Duration::from_millis(200));
+            ok("application/json", r#"{"ok":true}"#)
+        });
+
+        let client = ClientBuilder::new(&server.url)
+            .coalesce_gets(true)
+            .build()
+            .unwrap();
+        let get = || async {
+            let request = client.request(Method::GET, "/apps").build().unwrap();
+            let response = client.execute(request).await.unwrap();
+            deserialize_json::<serde_json::Value>(response)
+                .await
+                .unwrap()
+        };
+
+        let responses = futures::future::join_all((0..5).map(|_| get())).await;
+        assert!(responses.iter().all(|value| value["ok"] == true));
+        assert_eq!(server.requests().len(), 1);
+
+        // Requests sent after the first one completed are not coalesced with it.
+        get().await;
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_decode_content_limit() {
+        use std::io::Write;
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(&[0u8; 4096]).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        assert_eq!(decode_content(&gzipped, "gzip", 4096).unwrap().len(), 4096);
+        match decode_content(&gzipped, "gzip", 4095) {
+            Err(SdkError::UnexpectedResponse { detail }) => {
+                assert_eq!(detail, "`gzip` content decodes to more than 4095 bytes")
+            }
+            other => panic!("expected a decoded size error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_etag_cache() {
+        let revalidated = |request: &str| request.to_lowercase().contains("if-none-match: \"v1\"");
+        let server = serve_with(usize::MAX, move |request| {
+            if revalidated(request) {
+                response("304 Not Modified", &[("ETag", "\"v1\"")], "")
+            } else {
+                response(
+                    "200 OK",
+                    &[("Content-Type", "application/json"), ("ETag", "\"v1\"")],
+                    r#"{"version":1}"#,
+                )
+            }
+        });
+
+        let client = ClientBuilder::new(&server.url)
+            .enable_etag_cache(8)
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            let request = client.request(Method::GET, "/apps/my-app").build().unwrap();
+            let response = client.execute(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let value = deserialize_json::<serde_json::Value>(response)
+                .await
+                .unwrap();
+            assert_eq!(value["version"], 1);
+        }
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .filter(|request| revalidated(request))
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_etag_cache_skips_non_json_responses() {
+        let server = serve_with(2, |_| {
+            response(
+                "200 OK",
+                &[("Content-Type", "application/x-ndjson"), ("ETag", "\"v1\"")],
+                "{\"line\":1}\n",
+            )
+        });
+
+        let client = ClientBuilder::new(&server.url)
+            .enable_etag_cache(8)
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            let request = client.request(Method::GET, "/logs").build().unwrap();
+            client.execute(request).await.unwrap();
+        }
+        assert!(
+            server
+                .requests()
+                .iter()
+                .all(|request| !request.to_lowercase().contains("if-none-match"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_etag_cache_evicts_least_recently_used() {
+        let server = serve_with(usize::MAX, |request| {
+            if request.to_lowercase().contains("if-none-match") {
+                response("304 Not Modified", &[], "")
+            } else {
+                response(
+                    "200 OK",
+                    &[("Content-Type", "application/json"), ("ETag", "\"v1\"")],
+                    "{}",
+                )
+            }
+        });
+
+        let client = ClientBuilder::new(&server.url)
+            .enable_etag_cache(2)
+            .build()
+            .unwrap();
+        // `/a` is used again before `/c` is cached, so `/b` is evicted instead of `/a`.
+        for path in ["/a", "/b", "/a", "/c", "/a", "/b"] {
+            let request = client.request(Method::GET, path).build().unwrap();
+            client.execute(request).await.unwrap();
+        }
+        let revalidated = server
+            .requests()
+            .iter()
+            .map(|request| request.to_lowercase().contains("if-none-match"))
+            .collect::<Vec<_>>();
+        assert_eq!(revalidated, vec![false, false, true, false, true, false]);
+    }
+
+    #[tokio::test]
+    async fn test_on_request_complete() {
+        use std::sync::Mutex;
+
+        let server = serve(vec![response("404 Not Found", &[], "")]);
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let hook_events = events.clone();
+        let client = ClientBuilder::new(&server.url)
+            .on_request_complete(move |event| hook_events.lock().unwrap().push(event.clone()))
+            .build()
+            .unwrap();
+        let request = client.request(Method::GET, "/apps/my-app").build().unwrap();
+        assert!(
+            client
+                .execute_route(request, "/apps/{application}")
+                .await
+                .is_err()
+        );
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].method, Method::GET);
+        assert_eq!(events[0].path, "/apps/my-app");
+        assert_eq!(events[0].route, Some("/apps/{application}"));
+        assert_eq!(events[0].status, Some(StatusCode::NOT_FOUND));
+        assert!(events[0].is_error());
+    }
+
+    #[tokio::test]
+    async fn test_truncate_error_bodies() {
+        for (max_bytes, expected) in [
+            (5, "error"),
+            (7, "error "),
+            (8, "error é"),
+            (100, "error été"),
+        ] {
+            let server = serve(vec![response(
+                "500 Internal Server Error",
+                &[],
+                "error été",
+            )]);
+            let client = ClientBuilder::new(&server.url)
+                .truncate_error_bodies(max_bytes)
+                .build()
+                .unwrap();
+            let request = client.request(Method::GET, "/apps").build().unwrap();
+            match client.execute(request).await {
+                Err(SdkError::ServerError { message, .. }) => assert_eq!(message, expected),
+                other => panic!("expected a server error, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_base_path() {
+        for base_path in ["/tensorlake", "tensorlake/", "/tensorlake/"] {
+            let client = ClientBuilder::new("https://gw.corp/")
+                .base_path(base_path)
+                .build()
+                .unwrap();
+            let request = client
+                .request(Method::GET, "/v1/namespaces/default/applications")
+                .build()
+                .unwrap();
+            assert_eq!(
+                request.url().as_str(),
+                "https://gw.corp/tensorlake/v1/namespaces/default/applications"
+            );
+        }
+
+        let client = ClientBuilder::new("https://gw.corp")
+            .base_path("/")
+            .build()
+            .unwrap();
+        let request = client.request(Method::GET, "/v1/whoami").build().unwrap();
+        assert_eq!(request.url().as_str(), "https://gw.corp/v1/whoami");
+    }
+
+    #[tokio::test]
+    async fn test_redirect_policy() {
+        let target = serve(vec![response("200 OK", &[], "")]);
+        let target_url = format!("{}/moved", target.url);
+        let redirect = || response("302 Found", &[("Location", target_url.as_str())], "");
+
+        // Cross-host redirects are followed without the bearer token.
+        let origin = serve(vec![redirect()]);
+        let client = ClientBuilder::new(&origin.url)
+            .bearer_token("secret-token")
+            .build()
+            .unwrap();
+        let request = client.request(Method::GET, "/apps").build().unwrap();
+        client.execute(request).await.unwrap();
+        assert!(
+            origin.requests()[0]
+                .to_lowercase()
+                .contains("authorization: bearer secret-token")
+        );
+        let target_request = target.requests()[0].to_lowercase();
+        assert!(target_request.starts_with("get /moved "));
+        assert!(!target_request.contains("authorization"));
+
+        // Without redirects, the redirect response is an error.
+        let origin = serve(vec![redirect()]);
+        let client = ClientBuilder::new(&origin.url)
+            .redirect_policy(RedirectPolicy::None)
+            .build()
+            .unwrap();
+        let request = client.request(Method::GET, "/apps").build().unwrap();
+        let error = client.execute(request).await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                SdkError::ServerError {
+                    status: StatusCode::FOUND,
+                    ..
+                }
+            ),
+            "unexpected error: {error:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_server_api_version() {
+        let server = serve(vec![response(
+            "404 Not Found",
+            &[(SERVER_API_VERSION_HEADER, "1.4.0")],
+            "",
+        )]);
+
+        let client = ClientBuilder::new(&server.url).build().unwrap();
+        assert_eq!(client.server_api_version(), None);
+        let request = client.request(Method::GET, "/apps").build().unwrap();
+        assert!(client.clone().execute(request).await.is_err());
+        assert_eq!(client.server_api_version().as_deref(), Some("1.4.0"));
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_json_rejects_html() {
+        let response = http::Response::builder()
+            .header(CONTENT_TYPE, "text/html; charset=utf-8")
+            .body("<html><body><h1>502 Bad Gateway</h1></body></html>")
+            .unwrap();
+
+        let err = deserialize_json::<serde_json::Value>(Response::from(response))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, SdkError::UnexpectedResponse { ref detail } if detail.contains("text/html")),
+            "unexpected error: {err:?}"
+        );
+
+        let response = http::Response::builder()
+            .header(CONTENT_TYPE, "application/json; charset=utf-8")
+            .body(r#"{"ok":true}"#)
+            .unwrap();
+        let value = deserialize_json::<serde_json::Value>(Response::from(response))
+            .await
+            .unwrap();
+        assert_eq!(value["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_deserialize_json_empty_body() {
+        for body in ["", "\n"] {
+            let response = http::Response::builder()
+                .header(CONTENT_TYPE, "application/json")
+                .body(body)
+                .unwrap();
+            let err = deserialize_json::<serde_json::Value>(Response::from(response))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, SdkError::UnexpectedResponse { ref detail } if detail.starts_with("empty response body")),
+                "unexpected error: {err:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_proxy_url() {
+        let result = ClientBuilder::new("https://api.tensorlake.ai")
+            .proxy("not a proxy url")
+            .build();
+        assert!(matches!(result, Err(SdkError::ClientError(_))));
+    }
+
+    #[test]
+    fn test_append_user_agent() {
+        let builder =
+            ClientBuilder::new("https://api.tensorlake.ai").append_user_agent("my-cli/2.1");
+        assert_eq!(
+            builder.user_agent,
+            Some(format!(
+                "my-cli/2.1 (Tensorlake Cloud SDK/{})",
+                env!("CARGO_PKG_VERSION")
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(any(feature = "rustls-tls", feature = "native-tls"))]
+    fn test_invalid_root_certificate() {
+        let result = ClientBuilder::new("https://api.tensorlake.ai")
+            .add_root_certificate(b"not a certificate")
+            .build();
+        assert!(matches!(result, Err(SdkError::ClientError(_))));
+    }
+
+    #[test]
+    fn test_valid_proxy_url() {
+        let result = ClientBuilder::new("https://api.tensorlake.ai")
+            .proxy("http://proxy.internal:8080")
+            .build();
+        assert!(result.is_ok());
+    }
+}
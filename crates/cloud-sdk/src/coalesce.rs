@@ -0,0 +1,132 @@
+//! Coalescing of concurrent identical GET requests.
+use bytes::Bytes;
+use futures::{
+    FutureExt,
+    future::{BoxFuture, Shared},
+};
+use http::Extensions;
+use reqwest::{Method, Request, Response, StatusCode, Version, header::HeaderMap};
+use reqwest_middleware::ClientWithMiddleware;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// A response whose body has been read, so it can be shared between callers.
+#[derive(Debug)]
+pub(crate) struct BufferedResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) version: Version,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+impl BufferedResponse {
+    /// Read the body of a response.
+    pub(crate) async fn read(response: Response) -> Result<Self, reqwest::Error> {
+        let status = response.status();
+        let version = response.version();
+        let headers = response.headers().clone();
+        let body = response.bytes().await?;
+        Ok(Self {
+            status,
+            version,
+            headers,
+            body,
+        })
+    }
+
+    /// Build a new response with a copy of the buffered one.
+    pub(crate) fn to_response(&self) -> Response {
+        let mut builder = http::Response::builder()
+            .status(self.status)
+            .version(self.version);
+        if let Some(headers) = builder.headers_mut() {
+            headers.extend(self.headers.clone());
+        }
+        let response = builder
+            .body(self.body.clone())
+            .expect("status and headers come from a valid response");
+        Response::from(response)
+    }
+}
+
+/// `None` means the shared request failed before producing a response.
+type InFlight = Shared<BoxFuture<'static, Option<Arc<BufferedResponse>>>>;
+
+/// Shares a single in-flight request between concurrent identical GET requests.
+#[derive(Clone, Default)]
+pub(crate) struct GetCoalescer {
+    in_flight: Arc<Mutex<HashMap<String, InFlight>>>,
+}
+
+impl GetCoalescer {
+    /// Execute a request, sharing the response with identical GET requests in flight.
+    ///
+    /// Other requests are executed directly. If the shared request fails before
+    /// producing a response, each caller retries its own request so it gets
+    /// its own error.
+    pub(crate) async fn execute(
+        &self,
+        client: &ClientWithMiddleware,
+        request: Request,
+        extensions: &Extensions,
+    ) -> Result<Response, reqwest_middleware::Error> {
+        if request.method() != Method::GET {
+            return client
+                .execute_with_extensions(request, &mut extensions.clone())
+                .await;
+        }
+        let Some(shared_request) = request.try_clone() else {
+            return client
+                .execute_with_extensions(request, &mut extensions.clone())
+                .await;
+        };
+
+        let key = request_key(&request);
+        let in_flight = {
+            let mut in_flight = self.in_flight.lock().expect("coalescer lock poisoned");
+            in_flight
+                .entry(key.clone())
+                .or_insert_with(|| {
+                    let client = client.clone();
+                    let mut extensions = extensions.clone();
+                    let map = self.in_flight.clone();
+                    async move {
+                        let response = match client
+                            .execute_with_extensions(shared_request, &mut extensions)
+                            .await
+                        {
+                            Ok(response) => BufferedResponse::read(response).await.ok(),
+                            Err(_) => None,
+                        };
+                        map.lock().expect("coalescer lock poisoned").remove(&key);
+                        response.map(Arc::new)
+                    }
+                    .boxed()
+                    .shared()
+                })
+                .clone()
+        };
+
+        match in_flight.await {
+            Some(response) => Ok(response.to_response()),
+            None => {
+                client
+                    .execute_with_extensions(request, &mut extensions.clone())
+                    .await
+            }
+        }
+    }
+}
+
+/// Identical requests have the same URL and the same headers.
+fn request_key(request: &Request) -> String {
+    let mut headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| format!("{name}: {}", String::from_utf8_lossy(value.as_bytes())))
+        .collect::<Vec<_>>();
+    headers.sort();
+    format!("{}\n{}", request.url(), headers.join("\n"))
+}
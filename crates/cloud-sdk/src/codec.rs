@@ -0,0 +1,438 @@
+//! # Codecs
+//!
+//! Decoders for streaming response bodies, usable with
+//! [`FramedRead`](tokio_util::codec::FramedRead).
+
+use bytes::BytesMut;
+use futures::TryStreamExt;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+use tokio_util::{
+    codec::{Decoder, FramedRead},
+    io::StreamReader,
+};
+
+use crate::{client::EventStream, error::SdkError};
+
+/// Decoder for newline-delimited JSON (`application/x-ndjson`).
+///
+/// Each line is deserialized into a `T`. Blank lines are skipped, and a last
+/// line without a trailing newline is decoded when the input ends.
+///
+/// # Example
+///
+/// ```rust
+/// use futures::StreamExt;
+/// use tensorlake_cloud_sdk::codec::NdjsonDecoder;
+/// use tokio_util::codec::FramedRead;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let body: &[u8] = b"{\"id\":1}\n{\"id\":2}\n";
+/// let mut values = FramedRead::new(body, NdjsonDecoder::<serde_json::Value>::new());
+/// while let Some(value) = values.next().await {
+///     println!("{}", value?["id"]);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct NdjsonDecoder<T> {
+    /// How far the current buffer has already been searched for a newline.
+    next_index: usize,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T> NdjsonDecoder<T> {
+    /// Create a new decoder.
+    pub fn new() -> Self {
+        Self {
+            next_index: 0,
+            _item: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for NdjsonDecoder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for NdjsonDecoder<T> {
+    type Item = T;
+    type Error = SdkError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, SdkError> {
+        while let Some(offset) = src[self.next_index..].iter().position(|b| *b == b'\n') {
+            let line = src.split_to(self.next_index + offset + 1);
+            self.next_index = 0;
+            if let Some(item) = parse_line(&line)? {
+                return Ok(Some(item));
+            }
+        }
+        self.next_index = src.len();
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>, SdkError> {
+        if let Some(item) = self.decode(src)? {
+            return Ok(Some(item));
+        }
+        let line = src.split();
+        self.next_index = 0;
+        parse_line(&line)
+    }
+}
+
+/// Deserialize a line, or return `None` if it's blank.
+fn parse_line<T: DeserializeOwned>(line: &[u8]) -> Result<Option<T>, SdkError> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    let item = serde_json::from_slice(line)?;
+    Ok(Some(item))
+}
+
+/// Decoder for the elements of an array field of a JSON object, such as the
+/// `applications` of `{"applications": [...], "cursor": null}`.
+///
+/// Elements are deserialized one at a time as they're received, so the whole
+/// array is never held in memory. The other fields of the object are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use futures::StreamExt;
+/// use tensorlake_cloud_sdk::codec::JsonArrayDecoder;
+/// use tokio_util::codec::FramedRead;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let body: &[u8] = b"{\"items\":[{\"id\":1},{\"id\":2}],\"cursor\":null}";
+/// let decoder = JsonArrayDecoder::<serde_json::Value>::new("items");
+/// let mut items = FramedRead::new(body, decoder);
+/// while let Some(item) = items.next().await {
+///     println!("{}", item?["id"]);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct JsonArrayDecoder<T> {
+    field: String,
+    state: ArrayState,
+    /// How far the current buffer has already been searched for the end of an element.
+    next_index: usize,
+    /// Nesting depth in the root object while looking for the field, and in the
+    /// current element while reading the array.
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    /// The string at the top level of the root object being read, which may be a key.
+    key: Vec<u8>,
+    _item: PhantomData<fn() -> T>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ArrayState {
+    /// Looking for the field in the root object.
+    Seeking,
+    /// A string was read at the top level of the root object, which is a key
+    /// if it's followed by a colon.
+    AfterString { is_field: bool },
+    /// The key of the field was read, the array starts after the colon.
+    AfterField { colon: bool },
+    /// Reading the elements of the array.
+    InArray,
+    /// The array ended, or the body is invalid.
+    Done,
+}
+
+impl<T> JsonArrayDecoder<T> {
+    /// Create a decoder for the elements of the array in `field` of the root object.
+    pub fn new(field: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            state: ArrayState::Seeking,
+            next_index: 0,
+            depth: 0,
+            in_string: false,
+            escaped: false,
+            key: Vec::new(),
+            _item: PhantomData,
+        }
+    }
+
+    /// Scan the buffer for the start of the array, discarding what's scanned.
+    ///
+    /// Returns whether the array was found.
+    fn seek_array(&mut self, src: &mut BytesMut) -> Result<bool, SdkError> {
+        for index in 0..src.len() {
+            let byte = src[index];
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    if self.depth == 1 {
+                        self.state = ArrayState::AfterString {
+                            is_field: self.key == self.field.as_bytes(),
+                        };
+                    }
+                    continue;
+                }
+                if self.depth == 1 {
+                    self.key.push(byte);
+                }
+                continue;
+            }
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+
+            match self.state {
+                ArrayState::AfterString { is_field } => {
+                    if byte == b':' {
+                        self.state = if is_field {
+                            ArrayState::AfterField { colon: true }
+                        } else {
+                            ArrayState::Seeking
+                        };
+                        continue;
+                    }
+                    self.state = ArrayState::Seeking;
+                }
+                ArrayState::AfterField { .. } => {
+                    if byte != b'[' {
+                        self.state = ArrayState::Done;
+                        return Err(SdkError::UnexpectedResponse {
+                            detail: format!("expected `{}` to be an array", self.field),
+                        });
+                    }
+                    let _ = src.split_to(index + 1);
+                    self.state = ArrayState::InArray;
+                    self.depth = 0;
+                    return Ok(true);
+                }
+                _ => {}
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.key.clear();
+                }
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    if self.depth == 0 {
+                        self.state = ArrayState::Done;
+                        return Err(self.missing_array());
+                    }
+                }
+                _ => {}
+            }
+        }
+        src.clear();
+        Ok(false)
+    }
+
+    /// Scan the buffer for the end of the next element of the array.
+    fn next_element(&mut self, src: &mut BytesMut) -> Option<(BytesMut, bool)> {
+        for index in self.next_index..src.len() {
+            let byte = src[index];
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' | b'[' => self.depth += 1,
+                b'}' | b']' if self.depth > 0 => self.depth -= 1,
+                b',' | b']' if self.depth == 0 => {
+                    let mut element = src.split_to(index + 1);
+                    element.truncate(index);
+                    self.next_index = 0;
+                    return Some((element, byte == b']'));
+                }
+                _ => {}
+            }
+        }
+        self.next_index = src.len();
+        None
+    }
+
+    fn missing_array(&self) -> SdkError {
+        SdkError::UnexpectedResponse {
+            detail: format!("missing `{}` array", self.field),
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Decoder for JsonArrayDecoder<T> {
+    type Item = T;
+    type Error = SdkError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, SdkError> {
+        loop {
+            match self.state {
+                ArrayState::Done => {
+                    src.clear();
+                    return Ok(None);
+                }
+                ArrayState::InArray => {
+                    let Some((element, last)) = self.next_element(src) else {
+                        return Ok(None);
+                    };
+                    if last {
+                        self.state = ArrayState::Done;
+                    }
+                    let element = element.trim_ascii();
+                    if !element.is_empty() {
+                        return Ok(Some(serde_json::from_slice(element)?));
+                    }
+                }
+                _ => {
+                    if !self.seek_array(src)? {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>, SdkError> {
+        if let Some(item) = self.decode(src)? {
+            return Ok(Some(item));
+        }
+        match self.state {
+            ArrayState::Done => Ok(None),
+            ArrayState::InArray => {
+                self.state = ArrayState::Done;
+                Err(SdkError::UnexpectedResponse {
+                    detail: format!("response ended in the `{}` array", self.field),
+                })
+            }
+            _ => {
+                self.state = ArrayState::Done;
+                Err(self.missing_array())
+            }
+        }
+    }
+}
+
+/// Stream the newline-delimited JSON body of a response.
+pub(crate) fn ndjson_stream<T>(response: Response) -> EventStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let body = response.bytes_stream().map_err(std::io::Error::other);
+    EventStream::new(FramedRead::new(
+        StreamReader::new(body),
+        NdjsonDecoder::new(),
+    ))
+}
+
+/// Stream the elements of an array field of the JSON object in the body of a response.
+pub(crate) fn json_array_stream<T>(response: Response, field: &str) -> EventStream<T>
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    let body = response.bytes_stream().map_err(std::io::Error::other);
+    EventStream::new(FramedRead::new(
+        StreamReader::new(body),
+        JsonArrayDecoder::new(field),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        id: u32,
+    }
+
+    #[tokio::test]
+    async fn test_ndjson_decoder() {
+        let body: &[u8] = b"{\"id\":1}\r\n\n{\"id\":2}\n{\"id\":3}";
+        let items = FramedRead::new(body, NdjsonDecoder::<Item>::new())
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(items, vec![Item { id: 1 }, Item { id: 2 }, Item { id: 3 }]);
+    }
+
+    #[test]
+    fn test_ndjson_decoder_partial_line() {
+        let mut decoder = NdjsonDecoder::<Item>::new();
+        let mut buffer = BytesMut::from(&b"{\"id\":"[..]);
+        assert!(decoder.decode(&mut buffer).unwrap().is_none());
+        buffer.extend_from_slice(b"7}\n{\"id\"");
+        assert_eq!(decoder.decode(&mut buffer).unwrap(), Some(Item { id: 7 }));
+        assert!(decoder.decode(&mut buffer).unwrap().is_none());
+        assert!(matches!(
+            decoder.decode_eof(&mut buffer),
+            Err(SdkError::Json(_))
+        ));
+    }
+
+    fn decode_all(body: &[u8], field: &str, chunk_size: usize) -> Result<Vec<Item>, SdkError> {
+        let mut decoder = JsonArrayDecoder::<Item>::new(field);
+        let mut buffer = BytesMut::new();
+        let mut items = Vec::new();
+        for chunk in body.chunks(chunk_size) {
+            buffer.extend_from_slice(chunk);
+            while let Some(item) = decoder.decode(&mut buffer)? {
+                items.push(item);
+            }
+        }
+        while let Some(item) = decoder.decode_eof(&mut buffer)? {
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    #[test]
+    fn test_json_array_decoder() {
+        let body = br#"{"cursor": "items", "other": {"items": [{"id": 9}], "s": "]\"}"},
+            "items" : [ {"id": 1, "tags": ["a,b", "]"]}, {"id": 2} ,{"id":3}], "next": null}"#;
+        for chunk_size in [1, 2, 7, body.len()] {
+            let items = decode_all(body, "items", chunk_size).unwrap();
+            assert_eq!(items, vec![Item { id: 1 }, Item { id: 2 }, Item { id: 3 }]);
+        }
+        assert_eq!(decode_all(br#"{"items":[]}"#, "items", 1).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_json_array_decoder_errors() {
+        assert!(matches!(
+            decode_all(br#"{"other":[{"id":1}]}"#, "items", 4),
+            Err(SdkError::UnexpectedResponse { .. })
+        ));
+        assert!(matches!(
+            decode_all(br#"{"items":{"id":1}}"#, "items", 4),
+            Err(SdkError::UnexpectedResponse { .. })
+        ));
+        assert!(matches!(
+            decode_all(br#"{"items":[{"id":1},{"id""#, "items", 4),
+            Err(SdkError::UnexpectedResponse { .. })
+        ));
+        assert!(matches!(
+            decode_all(br#"{"items":[{"id":"one"}]}"#, "items", 4),
+            Err(SdkError::Json(_))
+        ));
+    }
+}
@@ -8,10 +8,40 @@ use reqwest_eventsource::CannotCloneRequestError;
 use thiserror::Error;
 
 use crate::{
-    applications::error::ApplicationsError, images::error::ImagesError,
+    applications::{error::ApplicationsError, models::RequestFailureReason},
+    images::error::ImagesError,
     secrets::error::SecretsError,
 };
 
+/// A coarse, stable classification of [`SdkError`].
+///
+/// Use it to map SDK errors to your own status codes without matching on every
+/// variant of [`SdkError`]. New kinds may be added in future versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SdkErrorKind {
+    /// The credentials are missing, invalid, or don't grant access to the resource.
+    Auth,
+    /// The requested resource doesn't exist.
+    NotFound,
+    /// The server is rejecting requests because too many were sent.
+    RateLimited,
+    /// The server failed to handle the request, or returned an unexpected response.
+    Server,
+    /// The server could not be reached, or the connection failed.
+    Network,
+    /// A payload could not be serialized or deserialized.
+    Serialization,
+    /// The request or the client configuration is invalid.
+    Client,
+    /// A server-sent event stream failed.
+    Stream,
+    /// The operation was cancelled by the caller.
+    Cancelled,
+    /// The operation didn't complete in time.
+    Timeout,
+}
+
 /// The main error type for the Tensorlake Cloud SDK.
 ///
 /// This enum encompasses all possible errors that can occur when using the SDK,
@@ -31,12 +61,16 @@ pub enum SdkError {
     Authorization(String),
 
     /// General HTTP errors
+    ///
+    /// Timeouts are reported as [`SdkError::Timeout`] instead.
     #[error(transparent)]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
 
     /// Reqwest middleware errors
+    ///
+    /// Timeouts are reported as [`SdkError::Timeout`] instead.
     #[error(transparent)]
-    Middleware(#[from] reqwest_middleware::Error),
+    Middleware(reqwest_middleware::Error),
 
     /// Errors specific to the Images client
     #[error(transparent)]
@@ -62,10 +96,23 @@ pub enum SdkError {
     #[error(transparent)]
     JsonWithError(#[from] serde_path_to_error::Error<serde_json::Error>),
 
+    /// YAML deserialization errors
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    Yaml(#[from] serde_path_to_error::Error<serde_yaml::Error>),
+
     /// Errors specific to the Secrets client
     #[error(transparent)]
     Secrets(#[from] SecretsError),
 
+    /// A request precondition was not met (HTTP 412)
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    /// Server returned a successful status, but the body was not what the SDK expected
+    #[error("Unexpected response: {detail}")]
+    UnexpectedResponse { detail: String },
+
     /// Server returned an error status
     #[error("Server error: {status} - {message}")]
     ServerError {
@@ -73,6 +120,29 @@ pub enum SdkError {
         message: String,
     },
 
+    /// A request of an application finished with a failure, see
+    /// [`Request::into_error`](crate::applications::models::Request::into_error).
+    #[error(
+        "Request {request_id} failed: {reason:?}{}",
+        failure_detail(function_name.as_deref(), message.as_deref())
+    )]
+    RequestFailed {
+        request_id: String,
+        reason: RequestFailureReason,
+        /// The function that failed, if the server reported it.
+        function_name: Option<String>,
+        /// The error message of the failure, if the server reported it.
+        message: Option<String>,
+    },
+
+    /// A long-running operation was cancelled through its cancellation token
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    /// An operation or HTTP request didn't complete within its timeout
+    #[error("Operation timed out")]
+    Timeout,
+
     /// Client returned an error initializing the EventSource stream
     #[error(transparent)]
     EventSourceConnectionError(#[from] CannotCloneRequestError),
@@ -81,3 +151,182 @@ pub enum SdkError {
     #[error(transparent)]
     EventSourceError(#[from] Box<reqwest_eventsource::Error>),
 }
+
+impl SdkError {
+    /// Classify this error into a [`SdkErrorKind`].
+    pub fn kind(&self) -> SdkErrorKind {
+        match self {
+            SdkError::Applications(error) => match error {
+                ApplicationsError::ApplicationNotFound { .. }
+                | ApplicationsError::FunctionCallNotFound { .. }
+                | ApplicationsError::RequestNotFound { .. } => SdkErrorKind::NotFound,
+                ApplicationsError::Http(error) => reqwest_error_kind(error),
+                ApplicationsError::Json(_) | ApplicationsError::InvalidOutputTypeHints(_) => {
+                    SdkErrorKind::Serialization
+                }
+                ApplicationsError::CannotRebuildManifest(_)
+                | ApplicationsError::CodeZip(_)
+                | ApplicationsError::InvalidRequest(_) => SdkErrorKind::Client,
+            },
+            SdkError::Authentication(_) | SdkError::Authorization(_) => SdkErrorKind::Auth,
+            SdkError::Http(error) => reqwest_error_kind(error),
+            SdkError::Middleware(reqwest_middleware::Error::Reqwest(error)) => {
+                reqwest_error_kind(error)
+            }
+            SdkError::Middleware(_) => SdkErrorKind::Network,
+            SdkError::Images(error) => match error {
+                ImagesError::BuildNotFound { .. } => SdkErrorKind::NotFound,
+                ImagesError::BuildFailed { .. } => SdkErrorKind::Server,
+                ImagesError::BuildTimeout { .. } => SdkErrorKind::Timeout,
+                ImagesError::Http(error) => reqwest_error_kind(error),
+                ImagesError::InvalidBuildRequest(_) => SdkErrorKind::Client,
+                ImagesError::Json(_) => SdkErrorKind::Serialization,
+            },
+            SdkError::InvalidHeaderValue(_) | SdkError::ClientError(_) | SdkError::Io(_) => {
+                SdkErrorKind::Client
+            }
+            SdkError::Json(_) | SdkError::JsonWithError(_) => SdkErrorKind::Serialization,
+            #[cfg(feature = "yaml")]
+            SdkError::Yaml(_) => SdkErrorKind::Serialization,
+            SdkError::Secrets(error) => match error {
+                SecretsError::OrganizationNotFound { .. }
+                | SecretsError::ProjectNotFound { .. }
+                | SecretsError::SecretNotFound { .. } => SdkErrorKind::NotFound,
+                SecretsError::Http(error) => reqwest_error_kind(error),
+                SecretsError::InvalidSecretData(_) | SecretsError::MissingScope(_) => {
+                    SdkErrorKind::Client
+                }
+                SecretsError::Json(_) => SdkErrorKind::Serialization,
+            },
+            SdkError::PreconditionFailed(_) => SdkErrorKind::Client,
+            SdkError::UnexpectedResponse { .. } => SdkErrorKind::Server,
+            SdkError::ServerError { status, .. } => status_kind(*status),
+            SdkError::RequestFailed { .. } => SdkErrorKind::Server,
+            SdkError::Cancelled => SdkErrorKind::Cancelled,
+            SdkError::Timeout => SdkErrorKind::Timeout,
+            SdkError::EventSourceConnectionError(_) | SdkError::EventSourceError(_) => {
+                SdkErrorKind::Stream
+            }
+        }
+    }
+}
+
+/// Describe where and why a request failed, for the message of [`SdkError::RequestFailed`].
+fn failure_detail(function_name: Option<&str>, message: Option<&str>) -> String {
+    match (function_name, message) {
+        (Some(function_name), Some(message)) => format!(" in `{function_name}`: {message}"),
+        (Some(function_name), None) => format!(" in `{function_name}`"),
+        (None, Some(message)) => format!(": {message}"),
+        (None, None) => String::new(),
+    }
+}
+
+impl From<reqwest::Error> for SdkError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_timeout() {
+            SdkError::Timeout
+        } else {
+            SdkError::Http(error)
+        }
+    }
+}
+
+impl From<reqwest_middleware::Error> for SdkError {
+    fn from(error: reqwest_middleware::Error) -> Self {
+        match error {
+            reqwest_middleware::Error::Reqwest(error) => SdkError::from(error),
+            error => SdkError::Middleware(error),
+        }
+    }
+}
+
+impl From<&SdkError> for SdkErrorKind {
+    fn from(error: &SdkError) -> Self {
+        error.kind()
+    }
+}
+
+fn reqwest_error_kind(error: &reqwest::Error) -> SdkErrorKind {
+    if error.is_timeout() {
+        SdkErrorKind::Timeout
+    } else if let Some(status) = error.status() {
+        status_kind(status)
+    } else if error.is_decode() {
+        SdkErrorKind::Serialization
+    } else if error.is_builder() {
+        SdkErrorKind::Client
+    } else {
+        SdkErrorKind::Network
+    }
+}
+
+fn status_kind(status: reqwest::StatusCode) -> SdkErrorKind {
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => SdkErrorKind::Auth,
+        reqwest::StatusCode::NOT_FOUND => SdkErrorKind::NotFound,
+        reqwest::StatusCode::TOO_MANY_REQUESTS => SdkErrorKind::RateLimited,
+        status if status.is_server_error() => SdkErrorKind::Server,
+        _ => SdkErrorKind::Client,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_reqwest_timeout_is_timeout() {
+        let server = crate::test_support::serve_nothing();
+
+        let error = reqwest::Client::new()
+            .get(&server.url)
+            .timeout(std::time::Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(matches!(SdkError::from(error), SdkError::Timeout));
+    }
+
+    #[test]
+    fn test_error_kind() {
+        let server_error = |status| SdkError::ServerError {
+            status,
+            message: String::new(),
+        };
+
+        assert_eq!(
+            SdkError::Authentication("bad token".to_string()).kind(),
+            SdkErrorKind::Auth
+        );
+        assert_eq!(
+            server_error(reqwest::StatusCode::NOT_FOUND).kind(),
+            SdkErrorKind::NotFound
+        );
+        assert_eq!(
+            server_error(reqwest::StatusCode::TOO_MANY_REQUESTS).kind(),
+            SdkErrorKind::RateLimited
+        );
+        assert_eq!(
+            server_error(reqwest::StatusCode::BAD_GATEWAY).kind(),
+            SdkErrorKind::Server
+        );
+        assert_eq!(
+            server_error(reqwest::StatusCode::BAD_REQUEST).kind(),
+            SdkErrorKind::Client
+        );
+        assert_eq!(
+            SdkErrorKind::from(&SdkError::from(ApplicationsError::RequestNotFound {
+                id: "request-123".to_string()
+            })),
+            SdkErrorKind::NotFound
+        );
+        assert_eq!(
+            SdkError::from(ImagesError::BuildTimeout { attempts: 3 }).kind(),
+            SdkErrorKind::Timeout
+        );
+        assert_eq!(
+            SdkError::from(serde_json::from_str::<u32>("nope").unwrap_err()).kind(),
+            SdkErrorKind::Serialization
+        );
+    }
+}
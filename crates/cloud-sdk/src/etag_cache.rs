@@ -0,0 +1,136 @@
+//! In-memory cache of GET responses, revalidated with their `ETag` or `Last-Modified` headers.
+use reqwest::{
+    Method, Request, Response, StatusCode,
+    header::{
+        ACCEPT, CONTENT_LENGTH, CONTENT_TYPE, ETAG, HeaderMap, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+        LAST_MODIFIED,
+    },
+};
+use reqwest_middleware::{Middleware, Next};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use crate::{client::is_json_content_type, coalesce::BufferedResponse};
+
+/// Middleware that caches JSON GET responses carrying an `ETag` or `Last-Modified` header.
+///
+/// Cached responses are revalidated with `If-None-Match` or `If-Modified-Since`,
+/// and served from the cache when the server answers `304 Not Modified`.
+/// When the cache is full, the least recently used entry is evicted.
+///
+/// Responses without a `Content-Length` are never cached, so that streamed
+/// responses are passed through without being buffered.
+pub(crate) struct EtagCache {
+    capacity: usize,
+    entries: Mutex<CacheEntries>,
+}
+
+#[derive(Default)]
+struct CacheEntries {
+    responses: HashMap<String, Arc<BufferedResponse>>,
+    order: VecDeque<String>,
+}
+
+impl EtagCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::default(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Arc<BufferedResponse>> {
+        let mut entries = self.entries.lock().expect("etag cache lock poisoned");
+        let response = entries.responses.get(key).cloned()?;
+        entries.touch(key);
+        Some(response)
+    }
+
+    fn insert(&self, key: String, response: Arc<BufferedResponse>) {
+        let mut entries = self.entries.lock().expect("etag cache lock poisoned");
+        if entries.responses.insert(key.clone(), response).is_some() {
+            entries.touch(&key);
+        } else {
+            entries.order.push_back(key);
+        }
+        while entries.order.len() > self.capacity {
+            if let Some(oldest) = entries.order.pop_front() {
+                entries.responses.remove(&oldest);
+            }
+        }
+    }
+}
+
+impl CacheEntries {
+    /// Move a key to the back of the eviction order, as the most recently used.
+    fn touch(&mut self, key: &str) {
+        if let Some(position) = self.order.iter().position(|entry| entry == key)
+            && let Some(key) = self.order.remove(position)
+        {
+            self.order.push_back(key);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for EtagCache {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut http::Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if req.method() != Method::GET || self.capacity == 0 {
+            return next.run(req, extensions).await;
+        }
+
+        let key = cache_key(&req);
+        let cached = self.get(&key);
+        if let Some(cached) = &cached {
+            let headers = req.headers_mut();
+            if let Some(etag) = cached.headers.get(ETAG) {
+                headers.entry(IF_NONE_MATCH).or_insert_with(|| etag.clone());
+            } else if let Some(last_modified) = cached.headers.get(LAST_MODIFIED) {
+                headers
+                    .entry(IF_MODIFIED_SINCE)
+                    .or_insert_with(|| last_modified.clone());
+            }
+        }
+
+        let response = next.run(req, extensions).await?;
+
+        match (response.status(), cached) {
+            (StatusCode::NOT_MODIFIED, Some(cached)) => Ok(cached.to_response()),
+            (StatusCode::OK, _) if is_cacheable(response.headers()) => {
+                let buffered = Arc::new(BufferedResponse::read(response).await?);
+                self.insert(key, buffered.clone());
+                Ok(buffered.to_response())
+            }
+            _ => Ok(response),
+        }
+    }
+}
+
+/// Whether a response can be revalidated, and is a JSON document read in full by
+/// its caller.
+fn is_cacheable(headers: &HeaderMap) -> bool {
+    let is_json = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(is_json_content_type);
+    is_json
+        && headers.contains_key(CONTENT_LENGTH)
+        && (headers.contains_key(ETAG) || headers.contains_key(LAST_MODIFIED))
+}
+
+/// Responses are cached by URL and accepted content type.
+fn cache_key(request: &Request) -> String {
+    let accept = request
+        .headers()
+        .get(ACCEPT)
+        .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+        .unwrap_or_default();
+    format!("{}\n{accept}", request.url())
+}
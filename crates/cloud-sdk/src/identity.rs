@@ -0,0 +1,25 @@
+//! # Tensorlake Cloud SDK - Identity
+//!
+//! Types describing the identity authenticated by the bearer token.
+
+use serde::{Deserialize, Serialize};
+
+/// The identity authenticated by the bearer token used by the SDK.
+///
+/// Project API keys are bound to an organization and a project, so both ids are
+/// always available for them. Personal Access Tokens are not bound to a project,
+/// so the project id, and possibly the organization id, are not set.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    /// The ID of the user that owns the token, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// The ID of the organization the token is scoped to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<String>,
+    /// The ID of the project the token is scoped to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+}
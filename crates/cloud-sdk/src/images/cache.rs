@@ -0,0 +1,80 @@
+//! Local cache of successful builds, keyed by what the build was submitted with.
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use super::models::{BuildInfo, ImageBuildRequest};
+
+/// Remembers the builds that succeeded for a build key, in memory and in a
+/// directory, so that identical builds can be reused instead of re-uploaded.
+///
+/// The cache is best effort: entries that can't be read or written are
+/// treated as missing.
+#[derive(Clone, Debug)]
+pub(crate) struct BuildCache {
+    dir: PathBuf,
+    builds: Arc<Mutex<HashMap<String, BuildInfo>>>,
+}
+
+impl BuildCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            builds: Arc::default(),
+        }
+    }
+
+    /// The key of a build, covering the application, version and function it's
+    /// submitted for, the image hash, and the context archive.
+    ///
+    /// The image hash alone doesn't identify a build: it's shared by the functions
+    /// using the same image, and doesn't cover the paths of the context files.
+    pub(crate) fn key(request: &ImageBuildRequest, image_hash: &str, context: &[u8]) -> String {
+        let mut hasher = Sha256::new();
+        for field in [
+            request.application_name.as_bytes(),
+            request.application_version.as_bytes(),
+            request.function_name.as_bytes(),
+            image_hash.as_bytes(),
+        ] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field);
+        }
+        hasher.update(Sha256::digest(context));
+        hex::encode(hasher.finalize())
+    }
+
+    /// The build recorded for a build key, if any.
+    pub(crate) fn get(&self, key: &str) -> Option<BuildInfo> {
+        let mut builds = self.builds.lock().expect("build cache lock poisoned");
+        if let Some(build) = builds.get(key) {
+            return Some(build.clone());
+        }
+
+        let contents = std::fs::read(self.entry_path(key)).ok()?;
+        let build: BuildInfo = serde_json::from_slice(&contents).ok()?;
+        builds.insert(key.to_string(), build.clone());
+        Some(build)
+    }
+
+    /// Record the build of a build key.
+    pub(crate) fn insert(&self, key: &str, build: &BuildInfo) {
+        self.builds
+            .lock()
+            .expect("build cache lock poisoned")
+            .insert(key.to_string(), build.clone());
+
+        if let Ok(contents) = serde_json::to_vec(build)
+            && std::fs::create_dir_all(&self.dir).is_ok()
+        {
+            let _ = std::fs::write(self.entry_path(key), contents);
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
@@ -30,32 +30,49 @@
 //! images_client.build_image(build_request);
 //! ```
 
-use std::{pin::Pin, time::Duration};
+use futures::{StreamExt, stream};
+use std::{path::PathBuf, time::Duration};
+use tokio_util::sync::CancellationToken;
 
-use crate::{client::Client, error::SdkError};
-use futures::stream::Stream;
+use crate::{
+    bulk::BulkResult,
+    client::{Client, EventStream, deserialize_json, urlencode},
+    error::SdkError,
+};
 use reqwest::{
     Method,
     multipart::{Form, Part},
 };
 
+mod cache;
 pub mod error;
 pub mod models;
+use cache::BuildCache;
+use error::ImagesError;
 use models::*;
 
+// Route templates of the endpoints, reported to metrics hooks and middlewares.
+const BUILDS_ROUTE: &str = "/images/v2/builds";
+const BUILD_ROUTE: &str = "/images/v2/builds/{build_id}";
+const CANCEL_BUILD_ROUTE: &str = "/images/v2/builds/{build_id}/cancel";
+
 /// A client for managing image builds in Tensorlake Cloud.
 #[derive(Clone)]
 pub struct ImagesClient {
     client: Client,
+    build_cache: Option<BuildCache>,
 }
 
 impl ImagesClient {
     /// Create a new images client.
     ///
+    /// Requests are sent to the URL set with
+    /// [`ClientBuilder::build_service_url`](crate::ClientBuilder::build_service_url),
+    /// or to the client's base URL if it's not set.
+    ///
     /// # Arguments
     ///
     /// * `client` - The base HTTP client configured with authentication
-    /// * `build_service_url` - The URL of the image build service
     ///
     /// # Example
     ///
@@ -71,7 +88,44 @@ impl ImagesClient {
     /// }
     /// ```
     pub fn new(client: Client) -> Self {
-        Self { client }
+        let client = match client.build_service_url() {
+            Some(url) => client.with_base_url(url),
+            None => client,
+        };
+        Self {
+            client,
+            build_cache: None,
+        }
+    }
+
+    /// Reuse successful builds of identical images instead of uploading them again.
+    ///
+    /// Builds are looked up by the application, version and function they're built
+    /// for, the image hash, and the files and paths of the build context. They're
+    /// looked up first among the builds of this client and then in `dir`, where
+    /// successful builds are recorded so they can be reused across runs. Reused
+    /// builds skip uploading the build context.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to record successful builds in
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tensorlake_cloud_sdk::{ClientBuilder, images::ImagesClient};
+    ///
+    /// fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let images_client = ImagesClient::new(client).with_build_cache(".tensorlake/builds");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_build_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.build_cache = Some(BuildCache::new(dir.into()));
+        self
     }
 
     /// Build a container image.
@@ -90,6 +144,8 @@ impl ImagesClient {
     /// # Errors
     ///
     /// Returns an error if the build request fails or the build process encounters an error.
+    /// Returns `SdkError::Cancelled` if the request's cancellation token is cancelled
+    /// before the build finishes.
     ///
     /// # Example
     ///
@@ -124,25 +180,250 @@ impl ImagesClient {
         &self,
         request: ImageBuildRequest,
     ) -> Result<ImageBuildResult, SdkError> {
-        let build_info = self.submit_build_request(&request).await?;
-        self.poll_build_status(&build_info.id).await
+        let (cache_key, build_info) = self.submit_or_reuse_build(&request).await?;
+        let result = self
+            .poll_build_status(&build_info.id, request.cancellation_token.as_ref())
+            .await?;
+        self.record_build(&cache_key, &build_info, &result);
+        Ok(result)
     }
 
-    /// Submit a build request to the build service.
-    async fn submit_build_request(
+    /// Build a container image and collect its build logs.
+    ///
+    /// This method submits an image build request, streams the build logs while
+    /// polling for completion, and returns both once the build finishes.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The image build request containing all necessary parameters
+    ///
+    /// # Returns
+    ///
+    /// Returns the build result and the build logs, ordered by sequence number.
+    /// Failed builds are returned with their logs, not as errors. Errors of the
+    /// log stream don't interrupt the build: the entries that couldn't be read
+    /// are missing from the logs.
+    ///
+    /// With `fail_fast_on_error_log` set on the request, the build is cancelled
+    /// as soon as a log entry reports an error, and returned as failed with the
+    /// logs received so far.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the build request fails, or if polling the build status fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::images::{ImagesClient, models::{BuildStatus, ImageBuildRequest}};
+    ///
+    /// async fn example(images_client: &ImagesClient, request: ImageBuildRequest) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let (result, logs) = images_client.build_image_with_logs(request).await?;
+    ///     if result.status == BuildStatus::Failed {
+    ///         for entry in logs {
+    ///             eprintln!("{}", entry.message);
+    ///         }
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn build_image_with_logs(
         &self,
-        request: &ImageBuildRequest,
-    ) -> Result<BuildInfo, SdkError> {
+        request: ImageBuildRequest,
+    ) -> Result<(ImageBuildResult, Vec<LogEntry>), SdkError> {
+        let (cache_key, build_info) = self.submit_or_reuse_build(&request).await?;
+
+        let logs_request = StreamLogsRequest {
+            build_id: build_info.id.clone(),
+            since_sequence: None,
+            auto_reconnect: true,
+        };
+        let mut stream = self
+            .stream_logs(&logs_request)
+            .await
+            .unwrap_or_else(|_| EventStream::new(stream::empty()));
+        let mut logs = Vec::new();
+
+        let poll = self.poll_build_status(&build_info.id, request.cancellation_token.as_ref());
+        tokio::pin!(poll);
+
+        let mut stream_ended = false;
+        let result = loop {
+            tokio::select! {
+                result = &mut poll => break result?,
+                entry = stream.next(), if !stream_ended => match entry {
+                    // The stream reconnects by itself, skip the entries it failed to read.
+                    Some(Err(_)) => {}
+                    Some(Ok(entry)) => {
+                        let failed = request.fail_fast_on_error_log && entry.reports_error();
+                        logs.push(entry);
+                        if failed {
+                            return self.fail_build_fast(&build_info, logs).await;
+                        }
+                    }
+                    None => stream_ended = true,
+                },
+            }
+        };
+
+        // Collect the entries sent while the final status was being fetched.
+        let drain_deadline = tokio::time::Instant::now() + LOG_DRAIN_TIMEOUT;
+        while !stream_ended {
+            match tokio::time::timeout_at(drain_deadline, stream.next()).await {
+                Ok(Some(Ok(entry))) => logs.push(entry),
+                Ok(Some(Err(_))) => {}
+                Ok(None) | Err(_) => stream_ended = true,
+            }
+        }
+
+        logs.sort_by_key(|entry| entry.sequence_number);
+        logs.dedup_by_key(|entry| entry.sequence_number);
+
+        self.record_build(&cache_key, &build_info, &result);
+        Ok((result, logs))
+    }
+
+    /// Plan an image build without submitting it.
+    ///
+    /// This method creates the build context archive locally and computes the image hash,
+    /// without contacting the build service. It's useful to decide whether a build is
+    /// necessary before calling [`ImagesClient::build_image`].
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The image build request to plan
+    ///
+    /// # Returns
+    ///
+    /// Returns the computed image hash and the size of the context archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the build context archive cannot be created.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tensorlake_cloud_sdk::{ClientBuilder, images::{ImagesClient, models::{ImageBuildRequest, Image}}};
+    ///
+    /// fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let images_client = ImagesClient::new(client);
+    ///
+    ///     let image = Image::builder()
+    ///         .name("my-app")
+    ///         .base_image("python:3.9")
+    ///         .build()?;
+    ///     let request = ImageBuildRequest::builder()
+    ///         .image(image)
+    ///         .image_tag("v1.0")
+    ///         .application_name("my-app")
+    ///         .application_version("1.0.0")
+    ///         .function_name("main")
+    ///         .sdk_version("0.2")
+    ///         .build()?;
+    ///
+    ///     let plan = images_client.plan_build(&request)?;
+    ///     println!("Image hash: {}, context size: {}", plan.image_hash, plan.context_bytes);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn plan_build(&self, request: &ImageBuildRequest) -> Result<BuildPlan, SdkError> {
         let mut context_data = Vec::new();
         request
             .image
             .create_context_archive(&mut context_data, &request.sdk_version)?;
+
+        Ok(BuildPlan {
+            image_hash: request.image.image_hash(&request.sdk_version),
+            context_bytes: context_data.len() as u64,
+        })
+    }
+
+    /// Submit a build request, unless a successful build of the same image is cached.
+    ///
+    /// Returns the build cache key with the build.
+    async fn submit_or_reuse_build(
+        &self,
+        request: &ImageBuildRequest,
+    ) -> Result<(String, BuildInfo), SdkError> {
         let image_hash = request.image.image_hash(&request.sdk_version);
+        let mut context_data = Vec::new();
+        request
+            .image
+            .create_context_archive(&mut context_data, &request.sdk_version)?;
+        let cache_key = BuildCache::key(request, &image_hash, &context_data);
+
+        let cached = self
+            .build_cache
+            .as_ref()
+            .and_then(|cache| cache.get(&cache_key));
+        let build_info = match cached {
+            Some(build_info) => build_info,
+            None => {
+                self.submit_build_request(request, &image_hash, context_data)
+                    .await?
+            }
+        };
+        Ok((cache_key, build_info))
+    }
+
+    /// Cancel a build whose logs reported an error, and report it as failed
+    /// with the message of the last entry.
+    async fn fail_build_fast(
+        &self,
+        build_info: &BuildInfo,
+        logs: Vec<LogEntry>,
+    ) -> Result<(ImageBuildResult, Vec<LogEntry>), SdkError> {
+        let cancel_request = models::CancelBuildRequest {
+            build_id: build_info.id.clone(),
+        };
+        let cancelled = self.cancel_build(&cancel_request).await;
+
+        // The build may already have finished on the server, and can't be cancelled anymore.
+        let current = self.fetch_build_info(&build_info.id).await?;
+        if let Err(error) = cancelled
+            && !BuildStatus::from(current.status.as_str()).is_terminal()
+        {
+            return Err(error);
+        }
+
+        let result = ImageBuildResult {
+            id: current.id,
+            status: BuildStatus::Failed,
+            created_at: current.created_at,
+            finished_at: current.finished_at,
+            error_message: logs
+                .last()
+                .map(|entry| entry.message.clone())
+                .or(current.error_message),
+        };
+        Ok((result, logs))
+    }
+
+    /// Record a successful build in the build cache, if it's enabled.
+    fn record_build(&self, cache_key: &str, build_info: &BuildInfo, result: &ImageBuildResult) {
+        if let Some(cache) = &self.build_cache
+            && result.status == BuildStatus::Succeeded
+        {
+            cache.insert(cache_key, build_info);
+        }
+    }
+
+    /// Submit a build request to the build service, with its context archive.
+    async fn submit_build_request(
+        &self,
+        request: &ImageBuildRequest,
+        image_hash: &str,
+        context_data: Vec<u8>,
+    ) -> Result<BuildInfo, SdkError> {
         let form = Form::new()
             .text("graph_name", request.application_name.clone())
             .text("graph_version", request.application_version.clone())
             .text("graph_function_name", request.function_name.clone())
-            .text("image_hash", image_hash)
+            .text("image_hash", image_hash.to_string())
             .text("image_name", request.image.name.clone())
             .part(
                 "context",
@@ -153,49 +434,54 @@ impl ImagesClient {
             self.client
                 .build_multipart_request(Method::PUT, "/images/v2/builds", form)?;
 
-        let response = self.client.execute(request).await?;
-        let json = response.json::<BuildInfo>().await?;
-
-        Ok(json)
+        let response = self.client.execute_route(request, BUILDS_ROUTE).await?;
+        deserialize_json::<BuildInfo>(response).await
     }
 
-    /// Poll the build status until completion.
-    async fn poll_build_status(&self, build_id: &str) -> Result<ImageBuildResult, SdkError> {
+    /// Poll the build status until completion, or until the cancellation token is cancelled.
+    async fn poll_build_status(
+        &self,
+        build_id: &str,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<ImageBuildResult, SdkError> {
         loop {
-            tokio::time::sleep(Duration::from_millis(100)).await;
+            let sleep = tokio::time::sleep(Duration::from_millis(100));
+            match cancellation_token {
+                Some(token) => tokio::select! {
+                    _ = token.cancelled() => return Err(SdkError::Cancelled),
+                    _ = sleep => {}
+                },
+                None => sleep.await,
+            }
 
-            let uri_str = format!("/images/v2/builds/{build_id}");
-            let request = self.client.request(Method::GET, &uri_str).build()?;
+            let build_info = self.fetch_build_info(build_id).await?;
+            let status = BuildStatus::from(build_info.status.as_str());
+            if !status.is_terminal() {
+                // Continue polling for other statuses (pending, building, canceling, etc.)
+                continue;
+            }
 
-            let response = self.client.execute(request).await?;
+            let error_message = match status {
+                BuildStatus::Succeeded => None,
+                _ => build_info.error_message,
+            };
+            return Ok(ImageBuildResult {
+                id: build_info.id,
+                status,
+                created_at: build_info.created_at,
+                finished_at: build_info.finished_at,
+                error_message,
+            });
+        }
+    }
 
-            let build_info: BuildInfo = response.json().await?;
+    /// Fetch the current state of a build.
+    async fn fetch_build_info(&self, build_id: &str) -> Result<BuildInfo, SdkError> {
+        let uri_str = format!("/images/v2/builds/{}", urlencode(build_id));
+        let request = self.client.request(Method::GET, &uri_str).build()?;
 
-            match build_info.status.as_str() {
-                "completed" | "succeeded" => {
-                    return Ok(ImageBuildResult {
-                        id: build_info.id,
-                        status: BuildStatus::Succeeded,
-                        created_at: build_info.created_at,
-                        finished_at: build_info.finished_at,
-                        error_message: None,
-                    });
-                }
-                "failed" => {
-                    return Ok(ImageBuildResult {
-                        id: build_info.id,
-                        status: BuildStatus::Failed,
-                        created_at: build_info.created_at,
-                        finished_at: build_info.finished_at,
-                        error_message: build_info.error_message,
-                    });
-                }
-                _ => {
-                    // Continue polling for other statuses (pending, in_progress, building, etc.)
-                    continue;
-                }
-            }
-        }
+        let response = self.client.execute_route(request, BUILD_ROUTE).await?;
+        deserialize_json(response).await
     }
 
     /// List builds for the current project.
@@ -210,7 +496,9 @@ impl ImagesClient {
     ///
     /// # Errors
     ///
-    /// Returns an error if the request fails or the response cannot be parsed.
+    /// Returns `ImagesError::InvalidBuildRequest` if the status filter is
+    /// [`BuildStatus::Unknown`], or an error if the request fails or the response
+    /// cannot be parsed.
     ///
     /// # Example
     ///
@@ -242,7 +530,6 @@ impl ImagesClient {
             query_params.push(("page_size", ps.to_string()));
         }
         if let Some(s) = &request.status {
-            // Assuming BuildStatus can be converted to string
             let status_str = match s {
                 BuildStatus::Pending => "pending",
                 BuildStatus::Enqueued => "enqueued",
@@ -251,6 +538,12 @@ impl ImagesClient {
                 BuildStatus::Failed => "failed",
                 BuildStatus::Canceling => "canceling",
                 BuildStatus::Canceled => "canceled",
+                BuildStatus::Unknown => {
+                    return Err(ImagesError::InvalidBuildRequest(
+                        "builds can't be filtered on an unknown status".to_string(),
+                    )
+                    .into());
+                }
             };
             query_params.push(("status", status_str.to_string()));
         }
@@ -263,6 +556,12 @@ impl ImagesClient {
         if let Some(gfn) = &request.function_name {
             query_params.push(("graph_function_name", gfn.to_string()));
         }
+        if let Some(hash) = &request.image_hash {
+            query_params.push(("image_hash", hash.to_string()));
+        }
+        for (key, value) in &request.extra_query {
+            query_params.push((key.as_str(), value.clone()));
+        }
 
         let req = self
             .client
@@ -270,9 +569,72 @@ impl ImagesClient {
             .query(&query_params)
             .build()?;
 
-        let response = self.client.execute(req).await?;
+        let response = self.client.execute_route(req, BUILDS_ROUTE).await?;
 
-        Ok(response.json::<Page<BuildListResponse>>().await?)
+        deserialize_json::<Page<BuildListResponse>>(response).await
+    }
+
+    /// Find the most recent successful build for an image hash.
+    ///
+    /// Use this together with [`ImagesClient::plan_build`] to skip builds whose
+    /// image hash has already been built by the build service.
+    ///
+    /// # Arguments
+    ///
+    /// * `image_hash` - The image hash to look up
+    ///
+    /// # Returns
+    ///
+    /// Returns the most recent succeeded build for the hash, or `None` if there isn't one.
+    /// All the pages of builds for the hash are searched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, images::ImagesClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let images_client = ImagesClient::new(client);
+    ///     if let Some(build) = images_client.find_build_by_hash("abc123").await? {
+    ///         println!("Found build: {}", build.public_id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn find_build_by_hash(
+        &self,
+        image_hash: &str,
+    ) -> Result<Option<BuildListResponse>, SdkError> {
+        let mut request = ListBuildsRequest::builder()
+            .status(BuildStatus::Succeeded)
+            .image_hash(image_hash)
+            .build()
+            .map_err(|error| ImagesError::InvalidBuildRequest(error.to_string()))?;
+
+        let mut latest = None;
+        loop {
+            let page = self.list_builds(&request).await?;
+            let next_page = page.next_page();
+            latest = latest
+                .into_iter()
+                .chain(
+                    page.items
+                        .into_iter()
+                        .filter(|build| build.status == BuildStatus::Succeeded),
+                )
+                .max_by_key(BuildListResponse::created_at);
+            match next_page {
+                Some(next_page) => request.page = Some(next_page),
+                None => return Ok(latest),
+            }
+        }
     }
 
     /// Cancel a build.
@@ -307,10 +669,10 @@ impl ImagesClient {
     /// }
     /// ```
     pub async fn cancel_build(&self, request: &models::CancelBuildRequest) -> Result<(), SdkError> {
-        let uri_str = format!("/images/v2/builds/{}/cancel", request.build_id);
+        let uri_str = format!("/images/v2/builds/{}/cancel", urlencode(&request.build_id));
         let req = self.client.request(Method::POST, &uri_str).build()?;
 
-        let _response = self.client.execute(req).await?;
+        let _response = self.client.execute_route(req, CANCEL_BUILD_ROUTE).await?;
 
         // 202 Accepted, no body
         Ok(())
@@ -351,16 +713,74 @@ impl ImagesClient {
         &self,
         request: &models::GetBuildInfoRequest,
     ) -> Result<BuildInfoResponse, SdkError> {
-        let uri_str = format!("/images/v2/builds/{}", request.build_id);
+        let uri_str = format!("/images/v2/builds/{}", urlencode(&request.build_id));
         let req = self.client.request(Method::GET, &uri_str).build()?;
 
-        let response = self.client.execute(req).await?;
+        let response = self.client.execute_route(req, BUILD_ROUTE).await?;
+
+        deserialize_json::<BuildInfoResponse>(response).await
+    }
+
+    /// Get information about several builds at once.
+    ///
+    /// The builds are fetched concurrently, with at most `concurrency` requests in
+    /// flight at the same time.
+    ///
+    /// # Arguments
+    ///
+    /// * `build_ids` - The IDs of the builds to get
+    /// * `concurrency` - The maximum number of requests in flight at the same time
+    ///
+    /// # Returns
+    ///
+    /// Returns the result of each lookup, in the same order as `build_ids`.
+    /// A failed lookup doesn't stop the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `concurrency` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::images::ImagesClient;
+    ///
+    /// async fn example(images_client: &ImagesClient) -> Result<(), Box<dyn std::error::Error>> {
+    ///     let build_ids = vec!["build-123".to_string(), "build-456".to_string()];
+    ///     for build in images_client.get_build_infos(&build_ids, 4).await? {
+    ///         println!("{:?}", build?.status);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_build_infos(
+        &self,
+        build_ids: &[String],
+        concurrency: usize,
+    ) -> Result<BulkResult<BuildInfoResponse>, SdkError> {
+        if concurrency == 0 {
+            return Err(ImagesError::InvalidBuildRequest(
+                "concurrency must be greater than zero".to_string(),
+            )
+            .into());
+        }
+
+        let lookups = build_ids.iter().map(|build_id| {
+            let request = GetBuildInfoRequest {
+                build_id: build_id.clone(),
+            };
+            async move { self.get_build_info(&request).await }
+        });
 
-        Ok(response.json::<BuildInfoResponse>().await?)
+        let results: Vec<_> = stream::iter(lookups).buffered(concurrency).collect().await;
+        Ok(results.into())
     }
 
     /// Stream build logs.
     ///
+    /// Set `since_sequence` on the request to the `sequence_number` of the last
+    /// [`LogEntry`] received to resume a stream without replaying earlier entries.
+    ///
     /// # Arguments
     ///
     /// * `request` - The stream logs request
@@ -401,14 +821,514 @@ impl ImagesClient {
         &self,
         request: &models::StreamLogsRequest,
     ) -> Result<ImageBuildLogStream, SdkError> {
-        let uri_str = format!("/images/v2/builds/{}/logs", request.build_id);
+        let stream = self
+            .open_log_stream(&request.build_id, request.since_sequence)
+            .await?;
+        if !request.auto_reconnect {
+            return Ok(stream);
+        }
+
+        let state = LogStreamState {
+            client: self.clone(),
+            build_id: request.build_id.clone(),
+            since_sequence: request.since_sequence,
+            stream: Some(stream),
+            reconnects: 0,
+            finished: false,
+        };
+        Ok(EventStream::new(stream::unfold(
+            state,
+            |mut state| async move {
+                let entry = state.next().await?;
+                Some((entry, state))
+            },
+        )))
+    }
+
+    async fn open_log_stream(
+        &self,
+        build_id: &str,
+        since_sequence: Option<i64>,
+    ) -> Result<ImageBuildLogStream, SdkError> {
+        let uri_str = format!("/images/v2/builds/{}/logs", urlencode(build_id));
+        let since_sequence = since_sequence.map(|seq| seq.to_string());
+        let query: Vec<_> = since_sequence
+            .iter()
+            .map(|seq| ("since_sequence", seq.as_str()))
+            .collect();
 
         let stream = self
             .client
-            .build_event_source_request::<LogEntry>(&uri_str)
+            .build_event_source_request_with_query::<LogEntry>(&uri_str, &query)
             .await?;
         Ok(stream)
     }
 }
 
-type ImageBuildLogStream = Pin<Box<dyn Stream<Item = Result<LogEntry, SdkError>> + Send>>;
+type ImageBuildLogStream = EventStream<LogEntry>;
+
+/// How many times in a row a build log stream is reopened before giving up.
+const MAX_LOG_STREAM_RECONNECTS: u32 = 5;
+
+/// How long to wait before reopening an interrupted build log stream.
+const LOG_STREAM_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+
+/// State of a build log stream that reconnects when it's interrupted.
+struct LogStreamState {
+    client: ImagesClient,
+    build_id: String,
+    since_sequence: Option<i64>,
+    stream: Option<ImageBuildLogStream>,
+    reconnects: u32,
+    finished: bool,
+}
+
+impl LogStreamState {
+    /// The next log entry, reconnecting until the build finishes or the
+    /// reconnection attempts are exhausted.
+    async fn next(&mut self) -> Option<Result<LogEntry, SdkError>> {
+        loop {
+            if self.finished {
+                return None;
+            }
+
+            let error = match &mut self.stream {
+                Some(stream) => match stream.next().await {
+                    Some(Ok(entry)) => {
+                        self.reconnects = 0;
+                        self.since_sequence = Some(entry.sequence_number);
+                        self.finished = entry.build_finished();
+                        return Some(Ok(entry));
+                    }
+                    Some(Err(error)) => Some(error),
+                    // The server closed the stream, which it also does once the build has
+                    // finished, even if the last entry was logged while it was running.
+                    None => match self.client.fetch_build_info(&self.build_id).await {
+                        Ok(build_info)
+                            if BuildStatus::from(build_info.status.as_str()).is_terminal() =>
+                        {
+                            self.finished = true;
+                            return None;
+                        }
+                        Ok(_) => None,
+                        Err(error) => Some(error),
+                    },
+                },
+                None => match self
+                    .client
+                    .open_log_stream(&self.build_id, self.since_sequence)
+                    .await
+                {
+                    Ok(stream) => {
+                        self.stream = Some(stream);
+                        continue;
+                    }
+                    Err(error) => Some(error),
+                },
+            };
+
+            self.stream = None;
+            if self.reconnects == MAX_LOG_STREAM_RECONNECTS {
+                self.finished = true;
+                return error.map(Err);
+            }
+            self.reconnects += 1;
+            tokio::time::sleep(LOG_STREAM_RECONNECT_DELAY).await;
+        }
+    }
+}
+
+/// How long to wait for trailing log entries once a build has finished.
+const LOG_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientBuilder, test_support::*};
+
+    const PENDING_BUILD: &str = r#"{"id":"build-1","status":"pending","created_at":"","updated_at":"","finished_at":null,"error_message":null}"#;
+
+    fn build_cache_key(request: &ImageBuildRequest) -> String {
+        let mut context_data = Vec::new();
+        request
+            .image
+            .create_context_archive(&mut context_data, &request.sdk_version)
+            .unwrap();
+        let image_hash = request.image.image_hash(&request.sdk_version);
+        BuildCache::key(request, &image_hash, &context_data)
+    }
+
+    fn build_request() -> ImageBuildRequest {
+        let image = Image::builder()
+            .name("my-app")
+            .base_image("python:3.12")
+            .build()
+            .unwrap();
+        ImageBuildRequest::builder()
+            .image(image)
+            .image_tag("latest")
+            .application_name("my-app")
+            .application_version("1.0.0")
+            .function_name("main")
+            .sdk_version("0.2")
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_poll_build_status_cancelled() {
+        let client = ClientBuilder::new("http://127.0.0.1:0").build().unwrap();
+        let images_client = ImagesClient::new(client);
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let result = images_client
+            .poll_build_status("build-123", Some(&token))
+            .await;
+        assert!(matches!(result, Err(SdkError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_poll_build_status_canceled_build() {
+        let server = serve_json(vec![
+            r#"{"id":"build-1","status":"canceling","created_at":"","updated_at":"","finished_at":null,"error_message":null}"#,
+            r#"{"id":"build-1","status":"canceled","created_at":"","updated_at":"","finished_at":"2024-01-01T00:00:00Z","error_message":"canceled by user"}"#,
+        ]);
+        let images_client = ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let result = images_client
+            .poll_build_status("build-1", None)
+            .await
+            .unwrap();
+        assert_eq!(result.status, BuildStatus::Canceled);
+        assert_eq!(result.error_message.as_deref(), Some("canceled by user"));
+    }
+
+    #[tokio::test]
+    async fn test_build_cache_reuses_successful_build() {
+        let server = serve_json(vec![
+            r#"{"id":"build-1","status":"succeeded","created_at":"","updated_at":"","finished_at":null,"error_message":null}"#,
+        ]);
+
+        let request = build_request();
+
+        let cache_dir = std::env::temp_dir().join(format!("build-cache-{}", rand::random::<u32>()));
+        BuildCache::new(cache_dir.clone()).insert(
+            &build_cache_key(&request),
+            &serde_json::from_str(PENDING_BUILD).unwrap(),
+        );
+
+        let client = ClientBuilder::new(&server.url).build().unwrap();
+        let images_client = ImagesClient::new(client).with_build_cache(&cache_dir);
+        let result = images_client.build_image(request).await.unwrap();
+        std::fs::remove_dir_all(&cache_dir).unwrap();
+
+        assert_eq!(result.id, "build-1");
+        assert_eq!(result.status, BuildStatus::Succeeded);
+        assert_eq!(
+            server.request_lines(),
+            vec!["GET /images/v2/builds/build-1 HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn test_plan_build() {
+        let images_client =
+            ImagesClient::new(ClientBuilder::new("http://127.0.0.1:0").build().unwrap());
+        let request = |sdk_version: &str| ImageBuildRequest {
+            sdk_version: sdk_version.to_string(),
+            ..build_request()
+        };
+
+        let plan = images_client.plan_build(&request("0.2")).unwrap();
+        assert_eq!(plan.image_hash, request("0.2").image.image_hash("0.2"));
+        assert!(plan.context_bytes > 0);
+        assert_eq!(plan, images_client.plan_build(&request("0.2")).unwrap());
+
+        let other = images_client.plan_build(&request("0.3")).unwrap();
+        assert_ne!(plan.image_hash, other.image_hash);
+    }
+
+    #[test]
+    fn test_build_cache_key() {
+        let root = std::env::temp_dir().join(format!("build-cache-key-{}", rand::random::<u32>()));
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.py"), "print('hello')").unwrap();
+
+        let request = |application_name: &str, function_name: &str| {
+            let mut request = ImageBuildRequest {
+                application_name: application_name.to_string(),
+                function_name: function_name.to_string(),
+                ..build_request()
+            };
+            request.image.build_operations = vec![
+                ImageBuildOperation::builder()
+                    .operation_type(ImageBuildOperationType::COPY)
+                    .args(vec![root.display().to_string(), "/app".to_string()])
+                    .build()
+                    .unwrap(),
+            ];
+            request
+        };
+
+        let key = build_cache_key(&request("my-app", "main"));
+        assert_eq!(key, build_cache_key(&request("my-app", "main")));
+        assert_ne!(key, build_cache_key(&request("other-app", "main")));
+        assert_ne!(key, build_cache_key(&request("my-app", "other")));
+
+        // Renaming a file keeps the image hash, but not the build key.
+        let image_hash = request("my-app", "main").image.image_hash("0.2");
+        std::fs::rename(root.join("a.py"), root.join("b.py")).unwrap();
+        let renamed = request("my-app", "main");
+        assert_eq!(renamed.image.image_hash("0.2"), image_hash);
+        assert_ne!(key, build_cache_key(&renamed));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_build_image_with_logs_fails_fast() {
+        let mut cancelled = false;
+        let server = serve_with(usize::MAX, move |request| {
+            let request_line = request.lines().next().unwrap_or_default();
+            if request_line.starts_with("PUT") {
+                ok("application/json", PENDING_BUILD)
+            } else if request_line.contains("/logs") {
+                ok(
+                    "text/event-stream",
+                    "data: {\"build_id\":\"build-1\",\"timestamp\":\"\",\"stream\":\"stdout\",\"message\":\"ERROR: this is only a warning\",\"sequence_number\":1,\"build_status\":\"building\"}\n\n\
+                     data: {\"build_id\":\"build-1\",\"timestamp\":\"\",\"stream\":\"error\",\"message\":\"pip install failed\",\"sequence_number\":2,\"build_status\":\"building\"}\n\n",
+                )
+            } else if request_line.contains("/cancel") {
+                cancelled = true;
+                response("202 Accepted", &[], "")
+            } else if cancelled {
+                ok(
+                    "application/json",
+                    r#"{"id":"build-1","status":"canceled","created_at":"","updated_at":"","finished_at":"2024-01-01T00:00:00Z","error_message":null}"#,
+                )
+            } else {
+                ok(
+                    "application/json",
+                    r#"{"id":"build-1","status":"building","created_at":"","updated_at":"","finished_at":null,"error_message":null}"#,
+                )
+            }
+        });
+
+        let request = ImageBuildRequest {
+            fail_fast_on_error_log: true,
+            ..build_request()
+        };
+
+        let images_client = ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let (result, logs) = images_client.build_image_with_logs(request).await.unwrap();
+
+        assert_eq!(result.status, BuildStatus::Failed);
+        assert_eq!(result.error_message.as_deref(), Some("pip install failed"));
+        assert_eq!(result.finished_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(logs.len(), 2);
+        assert_eq!(
+            server
+                .request_lines()
+                .into_iter()
+                .filter(|line| line.contains("/cancel"))
+                .collect::<Vec<_>>(),
+            vec!["POST /images/v2/builds/build-1/cancel HTTP/1.1"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_image_with_logs_skips_log_errors() {
+        let server = serve_with(usize::MAX, |request| {
+            let request_line = request.lines().next().unwrap_or_default();
+            if request_line.starts_with("PUT") {
+                ok("application/json", PENDING_BUILD)
+            } else if request_line.contains("/logs") {
+                response("503 Service Unavailable", &[], "")
+            } else {
+                ok(
+                    "application/json",
+                    r#"{"id":"build-1","status":"succeeded","created_at":"","updated_at":"","finished_at":null,"error_message":null}"#,
+                )
+            }
+        });
+
+        let images_client = ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let (result, logs) = images_client
+            .build_image_with_logs(build_request())
+            .await
+            .unwrap();
+
+        assert_eq!(result.status, BuildStatus::Succeeded);
+        assert!(logs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_responses() {
+        fn client() -> ImagesClient {
+            let server = serve_json(vec![""]);
+            ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap())
+        }
+        fn is_empty_body(error: SdkError) -> bool {
+            matches!(error, SdkError::UnexpectedResponse { detail } if detail.starts_with("empty response body"))
+        }
+
+        assert!(is_empty_body(
+            client().build_image(build_request()).await.unwrap_err()
+        ));
+
+        let poll = client().poll_build_status("build-1", None).await;
+        assert!(is_empty_body(poll.unwrap_err()));
+
+        let list = ListBuildsRequest::builder().build().unwrap();
+        assert!(is_empty_body(
+            client().list_builds(&list).await.unwrap_err()
+        ));
+
+        let get = GetBuildInfoRequest::builder()
+            .build_id("build-1")
+            .build()
+            .unwrap();
+        assert!(is_empty_body(
+            client().get_build_info(&get).await.unwrap_err()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_builds_rejects_unknown_status() {
+        let images_client =
+            ImagesClient::new(ClientBuilder::new("http://127.0.0.1:0").build().unwrap());
+        assert!(
+            ListBuildsRequest::builder()
+                .status(BuildStatus::Unknown)
+                .build()
+                .is_err()
+        );
+
+        let request = ListBuildsRequest {
+            status: Some(BuildStatus::Unknown),
+            ..ListBuildsRequest::builder().build().unwrap()
+        };
+        assert!(matches!(
+            images_client.list_builds(&request).await,
+            Err(SdkError::Images(ImagesError::InvalidBuildRequest(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_find_build_by_hash_searches_all_pages() {
+        let server = serve_json(vec![
+            r#"{"items":[{"public_id":"b1","name":"img","tags":[],"creation_time":"2024-01-02T00:00:00Z","status":"succeeded"},{"public_id":"b2","name":"img","tags":[],"creation_time":"2024-01-01T00:00:00Z","status":"succeeded"}],"total_items":3,"page":1,"page_size":2,"total_pages":2}"#,
+            r#"{"items":[{"public_id":"b3","name":"img","tags":[],"creation_time":"2024-01-02T00:00:00.5Z","status":"succeeded"}],"total_items":3,"page":2,"page_size":2,"total_pages":2}"#,
+        ]);
+        let images_client = ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap());
+
+        let build = images_client
+            .find_build_by_hash("abc123")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(build.public_id, "b3");
+        assert_eq!(server.request_lines().len(), 2);
+        assert!(server.request_lines()[1].contains("page=2"));
+    }
+
+    #[tokio::test]
+    async fn test_get_build_infos() {
+        let server = serve(vec![
+            ok(
+                "application/json",
+                r#"{"id":"build-1","status":"succeeded","error_message":null,"created_at":"","updated_at":"","finished_at":null,"image_hash":"hash","image_name":null}"#,
+            ),
+            response(
+                "404 Not Found",
+                &[("Content-Type", "application/json")],
+                r#"{"message":"not found"}"#,
+            ),
+        ]);
+
+        let images_client = ImagesClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let build_ids = vec!["build-1".to_string(), "missing".to_string()];
+        let results = images_client.get_build_infos(&build_ids, 1).await.unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(
+            results
+                .successes()
+                .map(|build| build.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["build-1"]
+        );
+        assert_eq!(
+            results
+                .failures()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "GET /images/v2/builds/build-1 HTTP/1.1",
+                "GET /images/v2/builds/missing HTTP/1.1",
+            ]
+        );
+
+        assert!(images_client.get_build_infos(&build_ids, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_logs_reconnects_after_last_entry() {
+        let log_entry = |sequence: u32, status: &str| {
+            ok(
+                "text/event-stream",
+                format!(
+                    "data: {{\"build_id\":\"build-1\",\"timestamp\":\"\",\"stream\":\"stdout\",\"message\":\"line {sequence}\",\"sequence_number\":{sequence},\"build_status\":\"{status}\"}}\n\n"
+                ),
+            )
+        };
+        let build = |status: &str| {
+            ok(
+                "application/json",
+                format!(
+                    r#"{{"id":"build-1","status":"{status}","created_at":"","updated_at":"","finished_at":null,"error_message":null}}"#
+                ),
+            )
+        };
+        // Both streams end while the build is running, the second one because it
+        // finished.
+        let server = serve(vec![
+            log_entry(1, "building"),
+            build("building"),
+            log_entry(2, "building"),
+            build("succeeded"),
+        ]);
+
+        let client = ClientBuilder::new(&server.url).build().unwrap();
+        let images_client = ImagesClient::new(client);
+        let request = StreamLogsRequest::builder()
+            .build_id("build-1")
+            .auto_reconnect(true)
+            .build()
+            .unwrap();
+        let entries = images_client
+            .stream_logs(&request)
+            .await
+            .unwrap()
+            .map(|entry| entry.unwrap().message)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(entries, vec!["line 1", "line 2"]);
+        assert_eq!(
+            server.request_lines(),
+            vec![
+                "GET /images/v2/builds/build-1/logs HTTP/1.1",
+                "GET /images/v2/builds/build-1 HTTP/1.1",
+                "GET /images/v2/builds/build-1/logs?since_sequence=1 HTTP/1.1",
+                "GET /images/v2/builds/build-1 HTTP/1.1",
+            ]
+        );
+    }
+}
@@ -1,12 +1,17 @@
 use derive_builder::Builder;
+use glob::Pattern;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::{self, Write};
+use tokio_util::sync::CancellationToken;
 use url;
 
+use crate::applications::models::Rfc3339DateTime;
+
 /// Internal representation of build information from the API.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct BuildInfo {
     pub id: String,
     pub status: String,
@@ -18,6 +23,7 @@ pub struct BuildInfo {
 
 /// Response for build info.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct BuildInfoResponse {
     /// The build ID.
     pub id: String,
@@ -35,10 +41,18 @@ pub struct BuildInfoResponse {
     pub image_hash: String,
     /// Image name.
     pub image_name: Option<String>,
+    /// Position of the build in the build queue while it's enqueued, starting
+    /// at 1 for the next build to start. `None` if the server doesn't report it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<i32>,
+    /// Estimated time at which an enqueued build starts, if the server reports it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimated_start_at: Option<String>,
 }
 
 /// Response for listing builds.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct BuildListResponse {
     /// The public ID of the build.
     pub public_id: String,
@@ -52,8 +66,15 @@ pub struct BuildListResponse {
     pub status: BuildStatus,
 }
 
+impl BuildListResponse {
+    /// The creation time of the build, or `None` if it isn't a valid timestamp.
+    pub fn created_at(&self) -> Option<Rfc3339DateTime> {
+        self.creation_time.parse().ok()
+    }
+}
+
 /// The status of an image build.
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum BuildStatus {
     /// The build is pending.
@@ -70,10 +91,49 @@ pub enum BuildStatus {
     Canceling,
     /// The build was canceled.
     Canceled,
+    /// A status this version of the SDK doesn't know about.
+    #[serde(other)]
+    Unknown,
+}
+
+impl BuildStatus {
+    /// Whether the build has finished, either successfully, with a failure, or canceled.
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            BuildStatus::Succeeded | BuildStatus::Failed | BuildStatus::Canceled
+        )
+    }
+
+    /// Whether the build is still waiting to run, running, or being canceled.
+    ///
+    /// [`BuildStatus::Unknown`] is neither terminal nor in progress.
+    pub fn is_in_progress(&self) -> bool {
+        matches!(
+            self,
+            BuildStatus::Pending
+                | BuildStatus::Enqueued
+                | BuildStatus::Building
+                | BuildStatus::Canceling
+        )
+    }
+}
+
+/// Parse a status as reported by the API, where `completed` is an older name of
+/// `succeeded`.
+impl From<&str> for BuildStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "completed" => BuildStatus::Succeeded,
+            status => serde_json::from_value(serde_json::Value::String(status.to_string()))
+                .unwrap_or(BuildStatus::Unknown),
+        }
+    }
 }
 
 /// Response for canceling a build.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct CancelBuildResponse {
     /// The status message.
     pub status: String,
@@ -99,6 +159,19 @@ pub struct ImageBuildRequest {
     /// The SDK version for hashing.
     #[builder(setter(into))]
     pub sdk_version: String,
+    /// Stop waiting for the build when this token is cancelled.
+    ///
+    /// Cancelling the token doesn't cancel the build on the server,
+    /// use [`ImagesClient::cancel_build`](crate::images::ImagesClient::cancel_build) for that.
+    #[builder(default, setter(strip_option))]
+    pub cancellation_token: Option<CancellationToken>,
+    /// Cancel the build as soon as a log entry reports an error, instead of
+    /// waiting for the build to fail.
+    ///
+    /// Only used by [`ImagesClient::build_image_with_logs`](crate::images::ImagesClient::build_image_with_logs),
+    /// which streams the logs. See [`LogEntry::reports_error`] for what counts as an error.
+    #[builder(default)]
+    pub fail_fast_on_error_log: bool,
 }
 
 impl ImageBuildRequest {
@@ -123,8 +196,18 @@ pub struct ImageBuildResult {
     pub error_message: Option<String>,
 }
 
+/// Result of planning an image build locally, without contacting the build service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuildPlan {
+    /// The hash computed from the image definition and SDK version.
+    pub image_hash: String,
+    /// The size in bytes of the compressed build context archive.
+    pub context_bytes: u64,
+}
+
 /// Response for pulling an image.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct ImagePullResponse {
     /// The build ID.
     pub id: String,
@@ -150,6 +233,7 @@ pub struct ImagePullResponse {
 
 /// Log entry for streaming logs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct LogEntry {
     /// The build ID.
     pub build_id: String,
@@ -165,8 +249,26 @@ pub struct LogEntry {
     pub build_status: String,
 }
 
+impl LogEntry {
+    /// Whether the build had finished when this entry was logged.
+    pub fn build_finished(&self) -> bool {
+        BuildStatus::from(self.build_status.as_str()).is_terminal()
+    }
+
+    /// Whether the entry reports an error: it's on the `error` stream, or was
+    /// logged once the build had failed.
+    ///
+    /// The message isn't inspected, since builds routinely log error-looking
+    /// lines that aren't fatal.
+    pub fn reports_error(&self) -> bool {
+        self.stream.eq_ignore_ascii_case("error")
+            || BuildStatus::from(self.build_status.as_str()) == BuildStatus::Failed
+    }
+}
+
 /// Paginated page of build list responses.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Page<T> {
     /// The items in this page.
     pub items: Vec<T>,
@@ -180,8 +282,25 @@ pub struct Page<T> {
     pub total_pages: i32,
 }
 
+impl<T> Page<T> {
+    /// Whether there are pages after this one.
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// Whether there are pages before this one. Pages are numbered from 1.
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+
+    /// The number of the next page, if there is one.
+    pub fn next_page(&self) -> Option<i32> {
+        self.has_next().then_some(self.page + 1)
+    }
+}
+
 /// Registry type for the image.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum RegistryType {
     /// ECR registry.
     ECR,
@@ -214,6 +333,7 @@ impl GetBuildInfoRequest {
 }
 
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct ListBuildsRequest {
     #[builder(default, setter(strip_option))]
     pub page: Option<i32>,
@@ -227,6 +347,12 @@ pub struct ListBuildsRequest {
     pub image_name: Option<String>,
     #[builder(default, setter(into, strip_option))]
     pub function_name: Option<String>,
+    #[builder(default, setter(into, strip_option))]
+    pub image_hash: Option<String>,
+    /// Additional query parameters, appended after the typed ones, for
+    /// server-side filters that don't have a typed field yet.
+    #[builder(default, setter(into))]
+    pub extra_query: Vec<(String, String)>,
 }
 
 impl ListBuildsRequest {
@@ -235,10 +361,26 @@ impl ListBuildsRequest {
     }
 }
 
+impl ListBuildsRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(Some(BuildStatus::Unknown)) = self.status {
+            return Err("builds can't be filtered on an unknown status".to_string());
+        }
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
 pub struct StreamLogsRequest {
     #[builder(setter(into))]
     pub build_id: String,
+    /// Only stream log entries after this sequence number.
+    #[builder(default, setter(strip_option))]
+    pub since_sequence: Option<i64>,
+    /// Reconnect when the stream is interrupted before the build finishes,
+    /// resuming after the last received entry.
+    #[builder(default)]
+    pub auto_reconnect: bool,
 }
 
 impl StreamLogsRequest {
@@ -248,7 +390,7 @@ impl StreamLogsRequest {
 }
 
 /// Type of image build operation.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ImageBuildOperationType {
     /// Copy files from the build context.
     COPY,
@@ -291,6 +433,15 @@ pub struct Image {
     /// List of build operations.
     #[builder(default)]
     pub build_operations: Vec<ImageBuildOperation>,
+    /// A Dockerfile to build the image from, instead of the base image and build operations.
+    #[builder(default, setter(into, strip_option))]
+    pub dockerfile: Option<String>,
+    /// Patterns for files to leave out of the build context, like `.git` or `*.pyc`.
+    ///
+    /// A pattern matches a file when it matches either its path relative to the
+    /// copied directory, or the name of any of its parent directories.
+    #[builder(default, setter(into))]
+    pub ignore_patterns: Vec<Pattern>,
 }
 
 impl Image {
@@ -298,20 +449,57 @@ impl Image {
         ImageBuilder::default()
     }
 
+    /// Create an image from an existing Dockerfile.
+    ///
+    /// The Dockerfile is added to the build context as is, so it must install
+    /// the `tensorlake` package itself. The base image is taken from the first
+    /// `FROM` instruction in the Dockerfile, skipping its flags such as `--platform`.
+    pub fn from_dockerfile(name: &str, dockerfile: &str) -> Self {
+        let base_image = dockerfile
+            .lines()
+            .map(str::trim)
+            .find_map(|line| {
+                let (instruction, args) = line.split_once(char::is_whitespace)?;
+                instruction
+                    .eq_ignore_ascii_case("FROM")
+                    .then(|| args.split_whitespace().find(|arg| !arg.starts_with("--")))
+                    .flatten()
+            })
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            name: name.to_string(),
+            base_image,
+            build_operations: Vec::new(),
+            dockerfile: Some(dockerfile.to_string()),
+            ignore_patterns: Vec::new(),
+        }
+    }
+
     /// Calculate the hash for this image, matching the Python implementation.
     pub fn image_hash(&self, sdk_version: &str) -> String {
         let mut hasher = Sha256::new();
         hasher.update(self.name.as_bytes());
         hasher.update(self.base_image.as_bytes());
         for op in &self.build_operations {
-            add_build_op_to_hasher(op, &mut hasher);
+            add_build_op_to_hasher(op, &self.ignore_patterns, &mut hasher);
+        }
+        if let Some(dockerfile) = &self.dockerfile {
+            hasher.update(dockerfile.as_bytes());
         }
         hasher.update(sdk_version.as_bytes());
         hex::encode(hasher.finalize())
     }
 
     /// Generate the Dockerfile content for this image.
+    ///
+    /// Images created from a Dockerfile return it unchanged.
     pub fn dockerfile_content(&self, sdk_version: &str) -> String {
+        if let Some(dockerfile) = &self.dockerfile {
+            return dockerfile.clone();
+        }
+
         let mut lines = vec![
             format!("FROM {}", self.base_image),
             "WORKDIR /app".to_string(),
@@ -336,6 +524,9 @@ impl Image {
     }
 
     /// Create a tar.gz archive containing the build context.
+    ///
+    /// The archive is reproducible: archiving the same sources twice yields the
+    /// same bytes, regardless of file timestamps or directory listing order.
     pub fn create_context_archive<W: Write>(&self, writer: W, sdk_version: &str) -> io::Result<()> {
         let gz_writer = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
         let mut tar = tar::Builder::new(gz_writer);
@@ -346,7 +537,7 @@ impl Image {
                     if let Some(src) = op.args.first()
                         && std::path::Path::new(src).exists()
                     {
-                        tar.append_dir_all(src, src)?;
+                        append_tree(&mut tar, src, &self.ignore_patterns)?;
                     }
                 }
                 ImageBuildOperationType::ADD => {
@@ -363,7 +554,10 @@ impl Image {
                             // Skip files inside .git directory
                             continue;
                         }
-                        tar.append_path(src)?;
+                        if is_ignored(std::path::Path::new(src), &self.ignore_patterns) {
+                            continue;
+                        }
+                        append_tree(&mut tar, src, &self.ignore_patterns)?;
                     }
                 }
                 _ => {} // Other operations don't add files
@@ -447,7 +641,74 @@ fn is_inside_git_dir(path: &str) -> bool {
         .any(|c| c.as_os_str() == ".git")
 }
 
-fn add_build_op_to_hasher(op: &ImageBuildOperation, hasher: &mut Sha256) {
+fn is_ignored(path: &std::path::Path, patterns: &[Pattern]) -> bool {
+    patterns.iter().any(|pattern| {
+        pattern.matches_path(path)
+            || path
+                .components()
+                .any(|c| pattern.matches(&c.as_os_str().to_string_lossy()))
+    })
+}
+
+/// Append a file or directory tree to the archive with normalized metadata.
+///
+/// Entries are added in path order with zeroed timestamps and ownership, and
+/// permissions reduced to `0o644` or `0o755`, so the same tree always produces
+/// the same archive.
+fn append_tree<W: Write>(
+    tar: &mut tar::Builder<W>,
+    src: &str,
+    patterns: &[Pattern],
+) -> io::Result<()> {
+    fn append_entry<W: Write>(
+        tar: &mut tar::Builder<W>,
+        root: &std::path::Path,
+        path: &std::path::Path,
+        patterns: &[Pattern],
+    ) -> io::Result<()> {
+        let metadata = std::fs::metadata(path)?;
+        let mut header = tar::Header::new_gnu();
+        header.set_metadata_in_mode(&metadata, tar::HeaderMode::Deterministic);
+        header.set_mtime(0);
+
+        // Archive paths must be relative: like Docker, absolute sources are
+        // resolved from the root of the build context.
+        let name = path
+            .components()
+            .filter(|component| {
+                !matches!(
+                    component,
+                    std::path::Component::Prefix(_) | std::path::Component::RootDir
+                )
+            })
+            .collect::<std::path::PathBuf>();
+
+        if !metadata.is_dir() {
+            return tar.append_data(&mut header, name, std::fs::File::open(path)?);
+        }
+
+        tar.append_data(&mut header, name, io::empty())?;
+
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+
+        for entry in entries {
+            let relative = entry.strip_prefix(root).unwrap_or(&entry);
+            if is_ignored(relative, patterns) {
+                continue;
+            }
+            append_entry(tar, root, &entry, patterns)?;
+        }
+        Ok(())
+    }
+
+    let root = std::path::Path::new(src);
+    append_entry(tar, root, root, patterns)
+}
+
+fn add_build_op_to_hasher(op: &ImageBuildOperation, patterns: &[Pattern], hasher: &mut Sha256) {
     hasher.update(op.operation_type.to_string().as_bytes());
 
     match op.operation_type {
@@ -460,24 +721,33 @@ fn add_build_op_to_hasher(op: &ImageBuildOperation, hasher: &mut Sha256) {
         }
         ImageBuildOperationType::COPY => {
             if let Some(src) = op.args.first() {
-                hash_directory(src, hasher);
+                hash_directory(src, patterns, hasher);
             }
         }
     }
 }
 
-fn hash_directory(path: &str, hasher: &mut Sha256) {
+fn hash_directory(path: &str, patterns: &[Pattern], hasher: &mut Sha256) {
     use std::fs;
     use std::io::Read;
 
-    fn visit_dir(dir: &std::path::Path, hasher: &mut Sha256) -> io::Result<()> {
+    fn visit_dir(
+        root: &std::path::Path,
+        dir: &std::path::Path,
+        patterns: &[Pattern],
+        hasher: &mut Sha256,
+    ) -> io::Result<()> {
         if dir.is_dir() {
-            let entries = fs::read_dir(dir)?;
-            for entry in entries {
-                let entry = entry?;
-                let path = entry.path();
+            let mut entries = fs::read_dir(dir)?
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<io::Result<Vec<_>>>()?;
+            entries.sort();
+            for path in entries {
+                if is_ignored(path.strip_prefix(root).unwrap_or(&path), patterns) {
+                    continue;
+                }
                 if path.is_dir() {
-                    visit_dir(&path, hasher)?;
+                    visit_dir(root, &path, patterns, hasher)?;
                 } else {
                     let mut file = fs::File::open(&path)?;
                     let mut buffer = [0u8; 1024];
@@ -496,6 +766,229 @@ fn hash_directory(path: &str, hasher: &mut Sha256) {
 
     let path = std::path::Path::new(path);
     if path.exists() {
-        visit_dir(path, hasher).unwrap();
+        visit_dir(path, path, patterns, hasher).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_from_dockerfile() {
+        let dockerfile =
+            "# syntax=docker/dockerfile:1\nFROM python:3.13 AS base\nRUN pip install tensorlake\n";
+        let image = Image::from_dockerfile("my-image", dockerfile);
+
+        assert_eq!(image.base_image, "python:3.13");
+        assert_eq!(image.dockerfile_content("0.2"), dockerfile);
+
+        let synthesized = Image::builder()
+            .name("my-image")
+            .base_image("python:3.13")
+            .build()
+            .unwrap();
+        assert_ne!(image.image_hash("0.2"), synthesized.image_hash("0.2"));
+
+        let image = Image::from_dockerfile(
+            "my-image",
+            "FROM --platform=linux/amd64 python:3.13-slim AS base\n",
+        );
+        assert_eq!(image.base_image, "python:3.13-slim");
+    }
+
+    #[test]
+    fn test_context_archive_ignore_patterns() {
+        use std::io::Read;
+
+        let root = std::env::temp_dir()
+            .join(format!("context-test-{}", rand::random::<u32>()))
+            .to_string_lossy()
+            .into_owned();
+        for dir in ["src/__pycache__", ".git", "data"] {
+            std::fs::create_dir_all(format!("{root}/{dir}")).unwrap();
+        }
+        for file in [
+            "src/app.py",
+            "src/__pycache__/app.pyc",
+            ".git/HEAD",
+            "data/large.bin",
+        ] {
+            std::fs::write(format!("{root}/{file}"), file).unwrap();
+        }
+
+        let image = Image::builder()
+            .name("my-image")
+            .base_image("python:3.13")
+            .build_operations(vec![
+                ImageBuildOperation::builder()
+                    .operation_type(ImageBuildOperationType::COPY)
+                    .args(vec![root.clone(), "/app".to_string()])
+                    .build()
+                    .unwrap(),
+            ])
+            .ignore_patterns(vec![
+                Pattern::new(".git").unwrap(),
+                Pattern::new("__pycache__").unwrap(),
+                Pattern::new("data/*").unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        let mut archive = Vec::new();
+        let result = image.create_context_archive(&mut archive, "0.2");
+        let unpacked = result.map(|_| {
+            let mut data = Vec::new();
+            flate2::read::GzDecoder::new(archive.as_slice())
+                .read_to_end(&mut data)
+                .unwrap();
+            tar::Archive::new(data.as_slice())
+                .entries()
+                .unwrap()
+                .map(|entry| {
+                    let entry = entry.unwrap();
+                    (
+                        entry.header().entry_type(),
+                        entry.path().unwrap().into_owned(),
+                    )
+                })
+                .filter(|(entry_type, _)| entry_type.is_file())
+                .map(|(_, path)| path.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+        });
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let mut files = unpacked.unwrap();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                "Dockerfile".to_string(),
+                format!("{}/src/app.py", root.trim_start_matches('/'))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_context_archive_is_deterministic() {
+        let root = std::env::temp_dir()
+            .join(format!("context-test-{}", rand::random::<u32>()))
+            .to_string_lossy()
+            .into_owned();
+        std::fs::create_dir_all(format!("{root}/pkg")).unwrap();
+        for file in ["b.py", "a.py", "pkg/c.py"] {
+            std::fs::write(format!("{root}/{file}"), file).unwrap();
+        }
+
+        let image = Image::builder()
+            .name("my-image")
+            .base_image("python:3.13")
+            .build_operations(vec![
+                ImageBuildOperation::builder()
+                    .operation_type(ImageBuildOperationType::COPY)
+                    .args(vec![root.clone(), "/app".to_string()])
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        let archive = || {
+            let mut data = Vec::new();
+            image.create_context_archive(&mut data, "0.2").map(|_| data)
+        };
+        let first = archive();
+        let touched = std::fs::File::options()
+            .write(true)
+            .open(format!("{root}/a.py"))
+            .and_then(|f| f.set_modified(std::time::SystemTime::now()));
+        let second = archive();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        touched.unwrap();
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn test_build_status_is_terminal() {
+        for status in [
+            BuildStatus::Succeeded,
+            BuildStatus::Failed,
+            BuildStatus::Canceled,
+        ] {
+            assert!(status.is_terminal(), "{status:?}");
+            assert!(!status.is_in_progress(), "{status:?}");
+        }
+        for status in [
+            BuildStatus::Pending,
+            BuildStatus::Enqueued,
+            BuildStatus::Building,
+            BuildStatus::Canceling,
+        ] {
+            assert!(!status.is_terminal(), "{status:?}");
+            assert!(status.is_in_progress(), "{status:?}");
+        }
+    }
+
+    #[test]
+    fn test_build_status_deserialization() {
+        for (json, status) in [
+            ("\"pending\"", BuildStatus::Pending),
+            ("\"enqueued\"", BuildStatus::Enqueued),
+            ("\"building\"", BuildStatus::Building),
+            ("\"succeeded\"", BuildStatus::Succeeded),
+            ("\"failed\"", BuildStatus::Failed),
+            ("\"canceling\"", BuildStatus::Canceling),
+            ("\"canceled\"", BuildStatus::Canceled),
+            ("\"queued_remote\"", BuildStatus::Unknown),
+        ] {
+            assert_eq!(serde_json::from_str::<BuildStatus>(json).unwrap(), status);
+        }
+
+        assert!(!BuildStatus::Unknown.is_terminal());
+        assert!(!BuildStatus::Unknown.is_in_progress());
+    }
+
+    #[test]
+    fn test_build_info_queue_position() {
+        let info: BuildInfoResponse = serde_json::from_str(
+            r#"{"id":"build-1","status":"enqueued","error_message":null,"created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z","finished_at":null,"image_hash":"abc","image_name":null,"queue_position":3,"estimated_start_at":"2024-01-01T00:05:00Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(info.queue_position, Some(3));
+        assert_eq!(
+            info.estimated_start_at.as_deref(),
+            Some("2024-01-01T00:05:00Z")
+        );
+
+        let info: BuildInfoResponse = serde_json::from_str(
+            r#"{"id":"build-1","status":"building","error_message":null,"created_at":"2024-01-01T00:00:00Z","updated_at":"2024-01-01T00:00:00Z","finished_at":null,"image_hash":"abc","image_name":null}"#,
+        )
+        .unwrap();
+        assert_eq!(info.queue_position, None);
+        assert_eq!(info.estimated_start_at, None);
+    }
+
+    #[test]
+    fn test_page_navigation() {
+        let page = |page, total_pages| Page::<()> {
+            items: Vec::new(),
+            total_items: 0,
+            page,
+            page_size: 10,
+            total_pages,
+        };
+
+        let first = page(1, 3);
+        assert!(first.has_next());
+        assert!(!first.has_prev());
+        assert_eq!(first.next_page(), Some(2));
+
+        let last = page(3, 3);
+        assert!(!last.has_next());
+        assert!(last.has_prev());
+        assert_eq!(last.next_page(), None);
+
+        assert_eq!(page(1, 0).next_page(), None);
     }
 }
@@ -18,7 +18,7 @@
 //!
 //!     // List applications in the default namespace
 //!     let request = ListApplicationsRequest::builder()
-//!         .namespace("default".to_string())
+//!         .namespace("default".into())
 //!         .build()?;
 //!     apps_client.list(&request).await?;
 //!     Ok(())
@@ -36,6 +36,34 @@
 //! let sdk = Sdk::new("https://api.tensorlake.ai", "your-token").unwrap();
 //! ```
 //!
+//! ## TLS Backends
+//!
+//! The SDK uses `rustls` by default. Enable the `native-tls` feature to use the
+//! platform's native TLS implementation instead:
+//!
+//! ```toml
+//! tensorlake-cloud-sdk = { version = "0.1", default-features = false, features = ["native-tls"] }
+//! ```
+//!
+//! ## Strict Deserialization
+//!
+//! Fields that the SDK doesn't know about are ignored in server responses. Enable the
+//! `strict-deserialization` feature to reject them instead, which is useful in tests
+//! to detect changes of the server's schema:
+//!
+//! ```toml
+//! tensorlake-cloud-sdk = { version = "0.1", features = ["strict-deserialization"] }
+//! ```
+//!
+//! ## YAML Manifests
+//!
+//! Enable the `yaml` feature to load application manifests from YAML files with
+//! `ApplicationManifest::from_yaml_file`:
+//!
+//! ```toml
+//! tensorlake-cloud-sdk = { version = "0.1", features = ["yaml"] }
+//! ```
+//!
 //! ## Available Clients
 //!
 //! - [`ApplicationsClient`](applications::ApplicationsClient): Manage applications, functions, and requests
@@ -54,7 +82,7 @@
 //!     let apps_client = sdk.applications();
 //!
 //!     let request = ListApplicationsRequest::builder()
-//!         .namespace("default".to_string())
+//!         .namespace("default".into())
 //!         .build()?;
 //!     match apps_client.list(&request).await {
 //!         Ok(apps) => println!("Success: {:?}", apps.applications.len()),
@@ -65,15 +93,27 @@
 //! ```
 
 pub mod applications;
+pub mod bulk;
+pub mod codec;
 pub mod error;
+pub mod identity;
 pub mod images;
+pub mod metrics;
 pub mod secrets;
 use applications::*;
 use images::*;
 use secrets::*;
 
+use reqwest::Method;
+
 mod client;
-pub use client::{Client, ClientBuilder};
+mod coalesce;
+mod etag_cache;
+#[cfg(test)]
+mod test_support;
+mod validation;
+use client::deserialize_json;
+pub use client::{Client, ClientBuilder, EventStream, RedirectPolicy, SERVER_API_VERSION_HEADER};
 
 /// The main entry point for the Tensorlake Cloud SDK.
 ///
@@ -164,6 +204,53 @@ impl Sdk {
         Ok(Self { client })
     }
 
+    /// Get the identity authenticated by the SDK's bearer token.
+    ///
+    /// For Project API keys, the identity includes the organization and project
+    /// the key belongs to, so you don't need to know them in advance.
+    ///
+    /// # Returns
+    ///
+    /// Returns the [`Identity`](identity::Identity) of the bearer token.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails or the response cannot be parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::Sdk;
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let sdk = Sdk::new("https://api.tensorlake.ai", "your-api-key")?;
+    ///     let identity = sdk.whoami().await?;
+    ///     println!("Project: {:?}", identity.project_id);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn whoami(&self) -> Result<identity::Identity, error::SdkError> {
+        let req = self
+            .client
+            .request(Method::GET, "/platform/v1/whoami")
+            .build()?;
+        let resp = self
+            .client
+            .execute_route(req, "/platform/v1/whoami")
+            .await?;
+
+        let identity = deserialize_json(resp).await?;
+
+        Ok(identity)
+    }
+
+    /// The API version reported by the server in the last response it sent, if any.
+    ///
+    /// See [`Client::server_api_version`].
+    pub fn server_api_version(&self) -> Option<String> {
+        self.client.server_api_version()
+    }
+
     /// Get a client for managing applications and requests.
     ///
     /// This method returns an [`ApplicationsClient`] that provides methods for:
@@ -186,7 +273,7 @@ impl Sdk {
     ///
     ///     // Use the applications client
     ///     let request = ListApplicationsRequest::builder()
-    ///         .namespace("default".to_string())
+    ///         .namespace("default".into())
     ///         .build()?;
     ///     apps_client.list(&request).await?;
     ///     Ok(())
@@ -0,0 +1,85 @@
+//! # Request Metrics
+//!
+//! Hooks to feed the HTTP calls of the SDK into a monitoring system, registered
+//! with [`ClientBuilder::on_request_complete`](crate::ClientBuilder::on_request_complete).
+use reqwest::{Method, Request, Response, StatusCode};
+use reqwest_middleware::{Middleware, Next};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A completed HTTP call, reported to the hook set with
+/// [`ClientBuilder::on_request_complete`](crate::ClientBuilder::on_request_complete).
+#[derive(Clone, Debug)]
+pub struct RequestMetricsEvent {
+    /// The HTTP method of the call.
+    pub method: Method,
+    /// The path of the URL of the call.
+    pub path: String,
+    /// The route template of the call, such as
+    /// `/v1/namespaces/{namespace}/applications/{application}`, if it's known.
+    ///
+    /// Group calls by route rather than by path to keep the cardinality of
+    /// metrics low.
+    pub route: Option<&'static str>,
+    /// The status of the response, or `None` if no response was received.
+    pub status: Option<StatusCode>,
+    /// How long the call took, until the response headers were received.
+    pub elapsed: Duration,
+}
+
+impl RequestMetricsEvent {
+    /// Whether the call failed, either without a response or with an error status.
+    pub fn is_error(&self) -> bool {
+        self.status
+            .is_none_or(|status| status.is_client_error() || status.is_server_error())
+    }
+}
+
+/// The route template of a request, set as a request extension.
+///
+/// The clients of the SDK tag their requests with it, so middlewares can read
+/// it from their `extensions` to group requests by route rather than by path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RouteTemplate(pub &'static str);
+
+/// Callback invoked with every completed HTTP call.
+pub(crate) type RequestMetricsHook = Arc<dyn Fn(&RequestMetricsEvent) + Send + Sync>;
+
+/// Middleware that reports every HTTP call to a [`RequestMetricsHook`].
+pub(crate) struct RequestMetrics {
+    hook: RequestMetricsHook,
+}
+
+impl RequestMetrics {
+    pub(crate) fn new(hook: RequestMetricsHook) -> Self {
+        Self { hook }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RequestMetrics {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut http::Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let method = req.method().clone();
+        let path = req.url().path().to_string();
+        let route = extensions.get::<RouteTemplate>().map(|route| route.0);
+        let started = Instant::now();
+
+        let result = next.run(req, extensions).await;
+
+        (self.hook)(&RequestMetricsEvent {
+            method,
+            path,
+            route,
+            status: result.as_ref().ok().map(Response::status),
+            elapsed: started.elapsed(),
+        });
+        result
+    }
+}
@@ -13,6 +13,10 @@ pub enum SecretsError {
     #[error("Invalid secret data: {0}")]
     InvalidSecretData(String),
 
+    /// Organization or project not set on the request nor on the client scope
+    #[error("Missing {0}: set it on the request or scope the client with ClientBuilder::scope")]
+    MissingScope(String),
+
     /// JSON serialization/deserialization error
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
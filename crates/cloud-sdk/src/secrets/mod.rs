@@ -28,15 +28,45 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! When the client is scoped with [`ClientBuilder::scope`](crate::ClientBuilder::scope),
+//! the organization and project can be omitted from the requests:
+//!
+//! ```rust,no_run
+//! use tensorlake_cloud_sdk::{ClientBuilder, Sdk, secrets::models::ListSecretsRequest};
+//!
+//! async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//!     let builder = ClientBuilder::new("https://api.tensorlake.ai")
+//!         .bearer_token("your-api-key")
+//!         .scope("org-id", "project-id");
+//!     let sdk = Sdk::with_client_builder(builder)?;
+//!
+//!     let list_req = ListSecretsRequest::builder().build()?;
+//!     sdk.secrets().list(&list_req).await?;
+//!     Ok(())
+//! }
+//! ```
 
 pub mod error;
 pub mod models;
 
-use crate::{client::Client, error::SdkError};
+use crate::{
+    client::{Client, deserialize_json, insert_headers, urlencode, with_timeout},
+    error::SdkError,
+    secrets::error::SecretsError,
+};
 
 use models::*;
 use reqwest::Method;
 
+// Route templates of the endpoints, reported to metrics hooks and middlewares.
+const SECRETS_ROUTE: &str =
+    "/platform/v1/organizations/{organization_id}/projects/{project_id}/secrets";
+const SECRET_ROUTE: &str =
+    "/platform/v1/organizations/{organization_id}/projects/{project_id}/secrets/{secret_id}";
+const VERIFY_SECRET_ROUTE: &str =
+    "/platform/v1/organizations/{organization_id}/projects/{project_id}/secrets/{secret_id}/verify";
+
 /// A client for managing secrets in Tensorlake Cloud.
 #[derive(Clone)]
 pub struct SecretsClient {
@@ -71,9 +101,7 @@ impl SecretsClient {
     ///
     /// # Arguments
     ///
-    /// * `organization_id` - The ID of the organization
-    /// * `project_id` - The ID of the project
-    /// * `upsert_secret` - The secret upsert request (single or multiple)
+    /// * `request` - The secret upsert request (single or multiple)
     ///
     /// # Returns
     ///
@@ -102,19 +130,25 @@ impl SecretsClient {
         &self,
         request: UpsertSecretRequest,
     ) -> Result<UpsertSecretResponse, SdkError> {
-        let uri_str = format!(
-            "/platform/v1/organizations/{}/projects/{}/secrets",
-            request.organization_id, request.project_id
-        );
+        with_timeout(request.timeout, self.upsert_inner(request)).await
+    }
 
-        let req = self
-            .client
-            .build_post_json_request(Method::PUT, &uri_str, &request.secrets)?;
-        let resp = self.client.execute(req).await?;
+    async fn upsert_inner(
+        &self,
+        request: UpsertSecretRequest,
+    ) -> Result<UpsertSecretResponse, SdkError> {
+        let uri_str = self.secrets_path(
+            request.organization_id.as_deref(),
+            request.project_id.as_deref(),
+        )?;
+
+        let mut req =
+            self.client
+                .build_post_json_request(Method::PUT, &uri_str, &request.secrets)?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, SECRETS_ROUTE).await?;
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let response = serde_path_to_error::deserialize(jd)?;
+        let response = deserialize_json(resp).await?;
 
         Ok(response)
     }
@@ -152,10 +186,17 @@ impl SecretsClient {
         &self,
         request: &models::ListSecretsRequest,
     ) -> Result<SecretsList, SdkError> {
-        let uri_str = format!(
-            "/platform/v1/organizations/{}/projects/{}/secrets",
-            request.organization_id, request.project_id
-        );
+        with_timeout(request.timeout, self.list_inner(request)).await
+    }
+
+    async fn list_inner(
+        &self,
+        request: &models::ListSecretsRequest,
+    ) -> Result<SecretsList, SdkError> {
+        let uri_str = self.secrets_path(
+            request.organization_id.as_deref(),
+            request.project_id.as_deref(),
+        )?;
 
         let mut req_builder = self.client.request(Method::GET, &uri_str);
 
@@ -169,12 +210,11 @@ impl SecretsClient {
             req_builder = req_builder.query(&[("pageSize", param_value)]);
         }
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, SECRETS_ROUTE).await?;
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let list = serde_path_to_error::deserialize(jd)?;
+        let list = deserialize_json(resp).await?;
 
         Ok(list)
     }
@@ -209,23 +249,89 @@ impl SecretsClient {
     /// }
     /// ```
     pub async fn get(&self, request: &models::GetSecretRequest) -> Result<Secret, SdkError> {
+        with_timeout(request.timeout, self.get_inner(request)).await
+    }
+
+    async fn get_inner(&self, request: &models::GetSecretRequest) -> Result<Secret, SdkError> {
         let uri_str = format!(
-            "/platform/v1/organizations/{}/projects/{}/secrets/{}",
-            request.organization_id, request.project_id, request.secret_id
+            "{}/{}",
+            self.secrets_path(
+                request.organization_id.as_deref(),
+                request.project_id.as_deref()
+            )?,
+            urlencode(&request.secret_id)
         );
 
         let req_builder = self.client.request(Method::GET, &uri_str);
 
-        let req = req_builder.build()?;
-        let resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let resp = self.client.execute_route(req, SECRET_ROUTE).await?;
 
-        let bytes = resp.bytes().await?;
-        let jd = &mut serde_json::Deserializer::from_reader(bytes.as_ref());
-        let secret = serde_path_to_error::deserialize(jd)?;
+        let secret = deserialize_json(resp).await?;
 
         Ok(secret)
     }
 
+    /// Check whether a secret has the expected value, without retrieving it.
+    ///
+    /// The candidate value is compared with the secret's value by the server, so
+    /// the secret's value is never sent back.
+    ///
+    /// # Arguments
+    ///
+    /// * `organization_id` - The organization of the secret
+    /// * `project_id` - The project of the secret
+    /// * `secret_id` - The ID of the secret
+    /// * `expected_value` - The value the secret is expected to have
+    ///
+    /// # Returns
+    ///
+    /// Returns whether the secret's value matches `expected_value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tensorlake_cloud_sdk::{ClientBuilder, secrets::SecretsClient};
+    ///
+    /// async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = ClientBuilder::new("https://api.tensorlake.ai")
+    ///         .bearer_token("your-api-key")
+    ///         .build()?;
+    ///     let secrets_client = SecretsClient::new(client);
+    ///     let rotated = secrets_client
+    ///         .verify("org-123", "proj-456", "secret-789", "new-value")
+    ///         .await?;
+    ///     println!("Rotated: {}", rotated);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn verify(
+        &self,
+        organization_id: &str,
+        project_id: &str,
+        secret_id: &str,
+        expected_value: &str,
+    ) -> Result<bool, SdkError> {
+        let uri_str = format!(
+            "{}/{}/verify",
+            self.secrets_path(Some(organization_id), Some(project_id))?,
+            urlencode(secret_id)
+        );
+
+        let body = VerifySecretBody {
+            value: expected_value,
+        };
+        let req = self
+            .client
+            .build_post_json_request(Method::POST, &uri_str, &body)?;
+        let resp = self.client.execute_route(req, VERIFY_SECRET_ROUTE).await?;
+
+        let response: VerifySecretResponse = deserialize_json(resp).await?;
+
+        Ok(response.matches)
+    }
+
     /// Delete a secret.
     ///
     /// # Arguments
@@ -252,16 +358,136 @@ impl SecretsClient {
     /// }
     /// ```
     pub async fn delete(&self, request: &models::DeleteSecretRequest) -> Result<(), SdkError> {
+        with_timeout(request.timeout, self.delete_inner(request)).await
+    }
+
+    async fn delete_inner(&self, request: &models::DeleteSecretRequest) -> Result<(), SdkError> {
         let uri_str = format!(
-            "/platform/v1/organizations/{}/projects/{}/secrets/{}",
-            request.organization_id, request.project_id, request.secret_id
+            "{}/{}",
+            self.secrets_path(
+                request.organization_id.as_deref(),
+                request.project_id.as_deref()
+            )?,
+            urlencode(&request.secret_id)
         );
 
         let req_builder = self.client.request(reqwest::Method::DELETE, &uri_str);
 
-        let req = req_builder.build()?;
-        let _resp = self.client.execute(req).await?;
+        let mut req = req_builder.build()?;
+        insert_headers(&mut req, &request.headers)?;
+        let _resp = self.client.execute_route(req, SECRET_ROUTE).await?;
 
         Ok(())
     }
+
+    /// Build the secrets path for a project, falling back to the client's scope
+    /// when the organization or project are not set on the request.
+    fn secrets_path(
+        &self,
+        organization_id: Option<&str>,
+        project_id: Option<&str>,
+    ) -> Result<String, SdkError> {
+        let organization_id = organization_id
+            .or(self.client.organization_id())
+            .ok_or_else(|| SecretsError::MissingScope("organization_id".to_string()))?;
+        let project_id = project_id
+            .or(self.client.project_id())
+            .ok_or_else(|| SecretsError::MissingScope("project_id".to_string()))?;
+
+        Ok(format!(
+            "/platform/v1/organizations/{}/projects/{}/secrets",
+            urlencode(organization_id),
+            urlencode(project_id)
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientBuilder, test_support::*};
+
+    #[test]
+    fn test_cloned_client_shares_scope() {
+        let client = ClientBuilder::new("https://api.tensorlake.ai")
+            .scope("org-123", "project-456")
+            .build()
+            .unwrap();
+        let secrets_client = SecretsClient::new(client);
+        let cloned = secrets_client.clone();
+
+        let expected = "/platform/v1/organizations/org-123/projects/project-456/secrets";
+        assert_eq!(secrets_client.secrets_path(None, None).unwrap(), expected);
+        assert_eq!(cloned.secrets_path(None, None).unwrap(), expected);
+        assert_eq!(
+            cloned.secrets_path(Some("other-org"), None).unwrap(),
+            "/platform/v1/organizations/other-org/projects/project-456/secrets"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_verify() {
+        let server = serve_json(vec![r#"{"matches":true}"#]);
+
+        let secrets_client = SecretsClient::new(ClientBuilder::new(&server.url).build().unwrap());
+        let matches = secrets_client
+            .verify("org-123", "proj-456", "secret-789", "new-value")
+            .await
+            .unwrap();
+        assert!(matches);
+
+        let request = &server.requests()[0];
+        assert!(request.starts_with(
+            "POST /platform/v1/organizations/org-123/projects/proj-456/secrets/secret-789/verify HTTP/1.1"
+        ));
+        assert!(request.ends_with(r#"{"value":"new-value"}"#));
+    }
+
+    #[tokio::test]
+    async fn test_empty_body_responses() {
+        fn client() -> SecretsClient {
+            let server = serve_json(vec![""]);
+            let client = ClientBuilder::new(&server.url)
+                .scope("org-123", "project-456")
+                .build()
+                .unwrap();
+            SecretsClient::new(client)
+        }
+        fn is_empty_body(error: SdkError) -> bool {
+            matches!(error, SdkError::UnexpectedResponse { detail } if detail.starts_with("empty response body"))
+        }
+
+        let upsert = UpsertSecretRequest::builder()
+            .secrets(("api-key", "secret123"))
+            .build()
+            .unwrap();
+        assert!(is_empty_body(client().upsert(upsert).await.unwrap_err()));
+
+        let list = ListSecretsRequest::builder().build().unwrap();
+        assert!(is_empty_body(client().list(&list).await.unwrap_err()));
+
+        let get = GetSecretRequest::builder()
+            .secret_id("secret-789")
+            .build()
+            .unwrap();
+        assert!(is_empty_body(client().get(&get).await.unwrap_err()));
+
+        let verify = client()
+            .verify("org-123", "project-456", "secret-789", "value")
+            .await;
+        assert!(is_empty_body(verify.unwrap_err()));
+    }
+
+    #[test]
+    fn test_cloned_client_without_scope() {
+        let client = ClientBuilder::new("https://api.tensorlake.ai")
+            .build()
+            .unwrap();
+        let cloned = SecretsClient::new(client).clone();
+
+        assert!(matches!(
+            cloned.secrets_path(None, Some("project-456")),
+            Err(SdkError::Secrets(SecretsError::MissingScope(field))) if field == "organization_id"
+        ));
+    }
 }
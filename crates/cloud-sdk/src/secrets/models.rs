@@ -1,7 +1,11 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::validation::validate_identifier;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Secret {
     pub id: String,
     pub name: String,
@@ -53,12 +57,20 @@ impl From<Vec<(&str, &str)>> for UpsertSecret {
 
 #[derive(Builder, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct UpsertSecretRequest {
-    #[builder(setter(into))]
-    pub organization_id: String,
-    #[builder(setter(into))]
-    pub project_id: String,
+    #[builder(default, setter(into, strip_option))]
+    pub organization_id: Option<String>,
+    #[builder(default, setter(into, strip_option))]
+    pub project_id: Option<String>,
     #[builder(setter(into))]
     pub secrets: UpsertSecret,
+    /// Headers added to this call only, such as a trace ID.
+    #[serde(skip)]
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[serde(skip)]
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl UpsertSecretRequest {
@@ -74,13 +86,37 @@ pub enum UpsertSecretResponse {
     Multiple(Vec<Secret>),
 }
 
+impl UpsertSecretResponse {
+    /// The upserted secrets, whether the server returned one secret or a list.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tensorlake_cloud_sdk::secrets::models::UpsertSecretResponse;
+    ///
+    /// let single: UpsertSecretResponse =
+    ///     serde_json::from_str(r#"{"id":"1","name":"KEY","createdAt":""}"#).unwrap();
+    /// let multiple: UpsertSecretResponse =
+    ///     serde_json::from_str(r#"[{"id":"1","name":"KEY","createdAt":""}]"#).unwrap();
+    /// assert_eq!(single.into_vec(), multiple.into_vec());
+    /// ```
+    pub fn into_vec(self) -> Vec<Secret> {
+        match self {
+            UpsertSecretResponse::Single(secret) => vec![secret],
+            UpsertSecretResponse::Multiple(secrets) => secrets,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct SecretsList {
     pub items: Vec<Secret>,
     pub pagination: Pagination,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
 pub struct Pagination {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<String>,
@@ -90,13 +126,20 @@ pub struct Pagination {
 }
 
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct DeleteSecretRequest {
-    #[builder(setter(into))]
-    pub organization_id: String,
-    #[builder(setter(into))]
-    pub project_id: String,
+    #[builder(default, setter(into, strip_option))]
+    pub organization_id: Option<String>,
+    #[builder(default, setter(into, strip_option))]
+    pub project_id: Option<String>,
     #[builder(setter(into))]
     pub secret_id: String,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl DeleteSecretRequest {
@@ -105,14 +148,28 @@ impl DeleteSecretRequest {
     }
 }
 
+impl DeleteSecretRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("secret_id", self.secret_id.as_ref())?;
+        Ok(())
+    }
+}
+
 #[derive(Builder, Debug)]
+#[builder(build_fn(validate = "Self::validate"))]
 pub struct GetSecretRequest {
-    #[builder(setter(into))]
-    pub organization_id: String,
-    #[builder(setter(into))]
-    pub project_id: String,
+    #[builder(default, setter(into, strip_option))]
+    pub organization_id: Option<String>,
+    #[builder(default, setter(into, strip_option))]
+    pub project_id: Option<String>,
     #[builder(setter(into))]
     pub secret_id: String,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl GetSecretRequest {
@@ -121,18 +178,44 @@ impl GetSecretRequest {
     }
 }
 
+impl GetSecretRequestBuilder {
+    fn validate(&self) -> Result<(), String> {
+        validate_identifier("secret_id", self.secret_id.as_ref())?;
+        Ok(())
+    }
+}
+
+/// Candidate value sent to the server to be compared with a secret's value.
+#[derive(Debug, Serialize)]
+pub(crate) struct VerifySecretBody<'a> {
+    pub(crate) value: &'a str,
+}
+
+/// Result of comparing a candidate value with a secret's value.
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "strict-deserialization", serde(deny_unknown_fields))]
+pub(crate) struct VerifySecretResponse {
+    pub(crate) matches: bool,
+}
+
 #[derive(Builder, Debug)]
 pub struct ListSecretsRequest {
-    #[builder(setter(into))]
-    pub organization_id: String,
-    #[builder(setter(into))]
-    pub project_id: String,
+    #[builder(default, setter(into, strip_option))]
+    pub organization_id: Option<String>,
+    #[builder(default, setter(into, strip_option))]
+    pub project_id: Option<String>,
     #[builder(default, setter(strip_option))]
     pub next: Option<String>,
     #[builder(default, setter(strip_option))]
     pub prev: Option<String>,
     #[builder(default, setter(strip_option))]
     pub page_size: Option<i32>,
+    /// Headers added to this call only, such as a trace ID.
+    #[builder(default, setter(into))]
+    pub headers: Vec<(String, String)>,
+    /// Fail with `SdkError::Timeout` if the call takes longer than this.
+    #[builder(default, setter(strip_option))]
+    pub timeout: Option<Duration>,
 }
 
 impl ListSecretsRequest {
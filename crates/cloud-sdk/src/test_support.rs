@@ -0,0 +1,169 @@
+//! Local HTTP servers for the unit tests.
+//!
+//! A server answers its connections one at a time, and closes each connection
+//! after its response. The requests it receives are recorded, so tests can check
+//! what the SDK sent once their calls have completed.
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A server listening on a local port.
+pub(crate) struct TestServer {
+    /// The base URL of the server.
+    pub(crate) url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl TestServer {
+    /// The requests received so far, including their headers and bodies.
+    pub(crate) fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The request lines of the requests received so far, such as `GET /path HTTP/1.1`.
+    pub(crate) fn request_lines(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .map(|request| request.lines().next().unwrap_or_default().to_string())
+            .collect()
+    }
+}
+
+/// A raw HTTP response with the given status line, headers, and body.
+pub(crate) fn response(status: &str, headers: &[(&str, &str)], body: impl AsRef<[u8]>) -> Vec<u8> {
+    let body = body.as_ref();
+    let mut response = format!("HTTP/1.1 {status}\r\n");
+    for (name, value) in headers {
+        response.push_str(&format!("{name}: {value}\r\n"));
+    }
+    response.push_str(&format!(
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    ));
+    let mut response = response.into_bytes();
+    response.extend_from_slice(body);
+    response
+}
+
+/// A `200 OK` response with a body of the given content type.
+pub(crate) fn ok(content_type: &str, body: impl AsRef<[u8]>) -> Vec<u8> {
+    response("200 OK", &[("Content-Type", content_type)], body)
+}
+
+/// Answer up to `connections` connections with the response returned by
+/// `handler` for each request.
+pub(crate) fn serve_with<F>(connections: usize, mut handler: F) -> TestServer
+where
+    F: FnMut(&str) -> Vec<u8> + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().take(connections) {
+            let mut stream = stream.unwrap();
+            let request = read_request(&mut stream);
+            recorded.lock().unwrap().push(request.clone());
+            // The client may have given up on the response already.
+            let _ = stream.write_all(&handler(&request));
+        }
+    });
+    TestServer { url, requests }
+}
+
+/// Answer successive connections with the given responses, in order.
+pub(crate) fn serve(responses: Vec<Vec<u8>>) -> TestServer {
+    let connections = responses.len();
+    let mut responses = responses.into_iter();
+    serve_with(connections, move |_| responses.next().unwrap())
+}
+
+/// Answer successive connections with `200 OK` responses of the given content
+/// types and bodies.
+pub(crate) fn serve_bodies(bodies: Vec<(&str, &str)>) -> TestServer {
+    serve(
+        bodies
+            .into_iter()
+            .map(|(content_type, body)| ok(content_type, body))
+            .collect(),
+    )
+}
+
+/// Answer successive connections with `200 OK` responses of the given JSON bodies.
+pub(crate) fn serve_json(bodies: Vec<&str>) -> TestServer {
+    serve(
+        bodies
+            .into_iter()
+            .map(|body| ok("application/json", body))
+            .collect(),
+    )
+}
+
+/// Answer a single connection with a JSON body and the given status.
+pub(crate) fn serve_once(status: &str, body: &str) -> TestServer {
+    serve(vec![response(
+        status,
+        &[("Content-Type", "application/json")],
+        body,
+    )])
+}
+
+/// Accept a single connection and never answer it, until the client closes it.
+pub(crate) fn serve_nothing() -> TestServer {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let mut buffer = [0u8; 4096];
+        while matches!(stream.read(&mut buffer), Ok(read) if read > 0) {}
+    });
+    TestServer {
+        url,
+        requests: Arc::default(),
+    }
+}
+
+/// Read a request, with its body when it has a `Content-Length` or is chunked.
+fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    let header_end = loop {
+        if let Some(end) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        let read = stream.read(&mut buffer).unwrap();
+        if read == 0 {
+            return String::from_utf8_lossy(&request).into_owned();
+        }
+        request.extend_from_slice(&buffer[..read]);
+    };
+
+    let headers = String::from_utf8_lossy(&request[..header_end]).to_lowercase();
+    let content_length = headers
+        .lines()
+        .find_map(|line| line.strip_prefix("content-length:"))
+        .and_then(|length| length.trim().parse::<usize>().ok());
+    let chunked = headers.contains("transfer-encoding: chunked");
+    loop {
+        let complete = match content_length {
+            Some(length) => request.len() >= header_end + length,
+            None => !chunked || request.ends_with(b"0\r\n\r\n"),
+        };
+        if complete {
+            break;
+        }
+        let read = stream.read(&mut buffer).unwrap();
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+    }
+    String::from_utf8_lossy(&request).into_owned()
+}
@@ -0,0 +1,18 @@
+//! Validation helpers shared by the request builders.
+
+/// Reject an identifier that is set but empty or only whitespace.
+///
+/// An empty identifier would produce a URL like `/v1/namespaces//applications`,
+/// which fails on the server with a confusing error. Unset fields are left to
+/// the builder, which reports them as uninitialized.
+pub(crate) fn validate_identifier<T: AsRef<str> + ?Sized>(
+    field: &str,
+    value: Option<&T>,
+) -> Result<(), String> {
+    match value {
+        Some(value) if value.as_ref().trim().is_empty() => {
+            Err(format!("{field} must not be empty or whitespace"))
+        }
+        _ => Ok(()),
+    }
+}
@@ -56,20 +56,13 @@ async fn test_applications_operations() {
             zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
         // Add code zip manifest
-        let manifest = r#"{
-            "functions": {
-                "simple_test_func": {
-                    "name": "simple_test_func",
-                    "module_import_name": "app"
-                },
-                "helper_func": {
-                    "name": "helper_func",
-                    "module_import_name": "app"
-                }
-            }
-        }"#;
+        let manifest = CodeManifest::new()
+            .function("simple_test_func", "app")
+            .function("helper_func", "app")
+            .to_json()
+            .unwrap();
         zip_writer
-            .start_file(".tensorlake_code_manifest.json", options)
+            .start_file(CODE_MANIFEST_FILE_NAME, options)
             .unwrap();
         zip_writer.write_all(manifest.as_bytes()).unwrap();
 
@@ -84,29 +77,10 @@ async fn test_applications_operations() {
     // Build application manifest
     let mut functions = HashMap::new();
 
-    let function_manifest = FunctionManifest::builder()
+    let function_manifest = FunctionManifest::builder_with_defaults()
         .name("simple_test_func")
         .description("A simple test function")
         .is_api(true)
-        .initialization_timeout_sec(300)
-        .timeout_sec(300)
-        .resources(
-            Resources::builder()
-                .cpus(1.0)
-                .memory_mb(1024)
-                .ephemeral_disk_mb(2048)
-                .build()
-                .unwrap(),
-        )
-        .retry_policy(
-            RetryPolicy::builder()
-                .max_retries(0)
-                .initial_delay_sec(1.0)
-                .max_delay_sec(60.0)
-                .delay_multiplier(2.0)
-                .build()
-                .unwrap(),
-        )
         .parameters(vec![
             Parameter::builder()
                 .name("input_text")
@@ -115,33 +89,12 @@ async fn test_applications_operations() {
                 .unwrap(),
         ])
         .return_type(return_type.clone())
-        .placement_constraints(PlacementConstraintsManifest::builder().build().unwrap())
-        .max_concurrency(1)
         .build()
         .unwrap();
 
-    let helper_function_manifest = FunctionManifest::builder()
+    let helper_function_manifest = FunctionManifest::builder_with_defaults()
         .name("helper_func")
         .is_api(false)
-        .initialization_timeout_sec(300)
-        .timeout_sec(300)
-        .resources(
-            Resources::builder()
-                .cpus(1.0)
-                .memory_mb(1024)
-                .ephemeral_disk_mb(2048)
-                .build()
-                .unwrap(),
-        )
-        .retry_policy(
-            RetryPolicy::builder()
-                .max_retries(0)
-                .initial_delay_sec(1.0)
-                .max_delay_sec(60.0)
-                .delay_multiplier(2.0)
-                .build()
-                .unwrap(),
-        )
         .parameters(vec![
             Parameter::builder()
                 .name("value")
@@ -150,8 +103,6 @@ async fn test_applications_operations() {
                 .unwrap(),
         ])
         .return_type(return_type)
-        .placement_constraints(PlacementConstraintsManifest::builder().build().unwrap())
-        .max_concurrency(1)
         .build()
         .unwrap();
 
@@ -178,7 +129,7 @@ async fn test_applications_operations() {
 
     // Create an application
     let upsert_request = UpsertApplicationRequest::builder()
-        .namespace(&project_id)
+        .namespace(Namespace::from(&project_id))
         .application_manifest(app_manifest)
         .code_zip(zip_data)
         .build()
@@ -191,7 +142,7 @@ async fn test_applications_operations() {
 
     // List applications
     let list_request = ListApplicationsRequest::builder()
-        .namespace(&project_id)
+        .namespace(Namespace::from(&project_id))
         .limit(100)
         .build()
         .unwrap();
@@ -210,8 +161,8 @@ async fn test_applications_operations() {
 
     // Get application
     let get_request = GetApplicationRequest::builder()
-        .namespace(&project_id)
-        .application(&application_name)
+        .namespace(Namespace::from(&project_id))
+        .application(ApplicationName::from(&application_name))
         .build()
         .unwrap();
 
@@ -224,8 +175,8 @@ async fn test_applications_operations() {
 
     // Invoke application
     let invoke_request = InvokeApplicationRequest::builder()
-        .namespace(&project_id)
-        .application(&application_name)
+        .namespace(Namespace::from(&project_id))
+        .application(ApplicationName::from(&application_name))
         .body(serde_json::json!({"input_text": "hello world"}))
         .build()
         .unwrap();
@@ -244,8 +195,8 @@ async fn test_applications_operations() {
 
     // List requests
     let list_requests_request = ListRequestsRequest::builder()
-        .namespace(&project_id)
-        .application(&application_name)
+        .namespace(Namespace::from(&project_id))
+        .application(ApplicationName::from(&application_name))
         .limit(10)
         .build()
         .unwrap();
@@ -260,12 +211,12 @@ async fn test_applications_operations() {
             .requests
             .iter()
             .map(|r| r.id.clone())
-            .any(|r| r == request_id)
+            .any(|r| request_id == r.as_str())
     );
 
     let delete_app = DeleteApplicationRequest::builder()
-        .namespace(&project_id)
-        .application(&application_name)
+        .namespace(Namespace::from(&project_id))
+        .application(ApplicationName::from(&application_name))
         .build()
         .unwrap();
 
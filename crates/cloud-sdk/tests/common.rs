@@ -4,7 +4,7 @@ use tensorlake_cloud_sdk::{Sdk, images::models::*};
 
 pub fn create_sdk() -> Sdk {
     let url = env::var("TENSORLAKE_API_URL").expect("TENSORLAKE_API_URL must be set");
-    let api_key = env::var("TENSORLAKE_API_KEY").expect("TENSORLAKE_API_TOKEN must be set");
+    let api_key = env::var("TENSORLAKE_API_KEY").expect("TENSORLAKE_API_KEY must be set");
 
     Sdk::new(&url, &api_key).expect("Failed to create SDK")
 }
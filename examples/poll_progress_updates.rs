@@ -40,7 +40,7 @@ use std::time::Duration;
 use tensorlake_cloud_sdk::Sdk;
 use tensorlake_cloud_sdk::applications::ApplicationsClient;
 use tensorlake_cloud_sdk::applications::models::{
-    ProgressUpdatesRequest, ProgressUpdatesRequestMode,
+    ApplicationName, Namespace, ProgressUpdatesRequest, RequestId,
 };
 
 #[tokio::main]
@@ -50,12 +50,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         env::var("TENSORLAKE_API_URL").expect("TENSORLAKE_API_URL environment variable not set");
     let api_key =
         env::var("TENSORLAKE_API_KEY").expect("TENSORLAKE_API_KEY environment variable not set");
-    let namespace = env::var("TENSORLAKE_NAMESPACE")
-        .expect("TENSORLAKE_NAMESPACE environment variable not set");
-    let application = env::var("TENSORLAKE_APPLICATION")
-        .expect("TENSORLAKE_APPLICATION environment variable not set");
-    let request_id = env::var("TENSORLAKE_REQUEST_ID")
-        .expect("TENSORLAKE_REQUEST_ID environment variable not set");
+    let namespace: Namespace = env::var("TENSORLAKE_NAMESPACE")
+        .expect("TENSORLAKE_NAMESPACE environment variable not set")
+        .into();
+    let application: ApplicationName = env::var("TENSORLAKE_APPLICATION")
+        .expect("TENSORLAKE_APPLICATION environment variable not set")
+        .into();
+    let request_id: RequestId = env::var("TENSORLAKE_REQUEST_ID")
+        .expect("TENSORLAKE_REQUEST_ID environment variable not set")
+        .into();
 
     let request_mode = env::var("TENSORLAKE_REQUEST_MODE").ok();
 
@@ -72,9 +75,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 async fn poll_for_updates(
     client: &ApplicationsClient,
-    namespace: &str,
-    application: &str,
-    request_id: &str,
+    namespace: &Namespace,
+    application: &ApplicationName,
+    request_id: &RequestId,
 ) -> Result<(), Box<dyn std::error::Error>> {
     // Initialize next_token for pagination
     let mut next_token: Option<String> = None;
@@ -82,13 +85,15 @@ async fn poll_for_updates(
     // Poll for updates every second
     println!("==> Polling for progress updates...");
     'outer: loop {
-        let request = ProgressUpdatesRequest::builder()
-            .namespace(namespace)
-            .application(application)
-            .request_id(request_id)
-            .mode(ProgressUpdatesRequestMode::Paginated(next_token.clone()))
-            .build()
-            .unwrap();
+        let mut request = ProgressUpdatesRequest::builder();
+        request
+            .namespace(namespace.clone())
+            .application(application.clone())
+            .request_id(request_id.clone());
+        if let Some(token) = &next_token {
+            request.paginated_from(token);
+        }
+        let request = request.build().unwrap();
 
         let response = client.get_progress_updates(&request).await?;
         let progress_updates = response.json();
@@ -117,15 +122,15 @@ async fn poll_for_updates(
 
 async fn stream_updates(
     client: &ApplicationsClient,
-    namespace: &str,
-    application: &str,
-    request_id: &str,
+    namespace: &Namespace,
+    application: &ApplicationName,
+    request_id: &RequestId,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let request = ProgressUpdatesRequest::builder()
-        .namespace(namespace)
-        .application(application)
-        .request_id(request_id)
-        .mode(ProgressUpdatesRequestMode::Stream)
+        .namespace(namespace.clone())
+        .application(application.clone())
+        .request_id(request_id.clone())
+        .stream()
         .build()
         .unwrap();
 